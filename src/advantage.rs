@@ -0,0 +1,108 @@
+use std::cmp;
+
+use character::Character;
+use conditions::Condition;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum RollMode {
+    #[default]
+    Normal,
+    Advantage,
+    Disadvantage,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AdvantageSource {
+    HiddenAttacker,
+    ProneTarget,
+    ProneAttacker,
+    BlindedAttacker,
+    RestrainedTarget,
+    RestrainedAttacker,
+}
+
+impl AdvantageSource {
+    pub fn grants(&self) -> RollMode {
+        match *self {
+            AdvantageSource::HiddenAttacker |
+            AdvantageSource::ProneTarget |
+            AdvantageSource::RestrainedTarget => RollMode::Advantage,
+            AdvantageSource::ProneAttacker |
+            AdvantageSource::BlindedAttacker |
+            AdvantageSource::RestrainedAttacker => RollMode::Disadvantage,
+        }
+    }
+}
+
+impl RollMode {
+    // Any number of advantage sources cancel out any number of disadvantage
+    // sources, leaving a normal roll.
+    pub fn from_sources(sources: &[AdvantageSource]) -> Self {
+        let advantage = sources.iter().any(|source| source.grants() == RollMode::Advantage);
+        let disadvantage = sources
+            .iter()
+            .any(|source| source.grants() == RollMode::Disadvantage);
+
+        match (advantage, disadvantage) {
+            (true, false) => RollMode::Advantage,
+            (false, true) => RollMode::Disadvantage,
+            _ => RollMode::Normal,
+        }
+    }
+
+    pub fn keep(&self, first: u32, second: u32) -> u32 {
+        match *self {
+            RollMode::Normal => first,
+            RollMode::Advantage => cmp::max(first, second),
+            RollMode::Disadvantage => cmp::min(first, second),
+        }
+    }
+}
+
+impl Character {
+    // A source whose condition is active already counts through that
+    // condition's flat modifiers, so it does not grant a second die as well.
+    pub fn roll_mode_against(&self, attackee: &Character, sources: &[AdvantageSource]) -> RollMode {
+        let sources: Vec<AdvantageSource> = sources
+            .iter()
+            .cloned()
+            .filter(|source| match *source {
+                AdvantageSource::ProneTarget => !attackee.conditions.has(Condition::Prone),
+                AdvantageSource::ProneAttacker => !self.conditions.has(Condition::Prone),
+                AdvantageSource::BlindedAttacker => !self.conditions.has(Condition::Blinded),
+                _ => true,
+            })
+            .collect();
+
+        RollMode::from_sources(&sources)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advantage_and_disadvantage_cancel_out() {
+        assert_eq!(RollMode::Normal, RollMode::from_sources(&[]));
+        assert_eq!(
+            RollMode::Advantage,
+            RollMode::from_sources(&[AdvantageSource::HiddenAttacker, AdvantageSource::ProneTarget])
+        );
+        assert_eq!(
+            RollMode::Normal,
+            RollMode::from_sources(&[
+                AdvantageSource::HiddenAttacker,
+                AdvantageSource::ProneTarget,
+                AdvantageSource::BlindedAttacker,
+            ])
+        );
+    }
+
+    #[test]
+    fn the_roll_mode_picks_which_die_to_keep() {
+        assert_eq!(4, RollMode::Normal.keep(4, 17));
+        assert_eq!(17, RollMode::Advantage.keep(4, 17));
+        assert_eq!(4, RollMode::Disadvantage.keep(4, 17));
+    }
+}
@@ -0,0 +1,220 @@
+use std::cmp;
+
+use character::{Character, Class, Race};
+use combat::DiceRollModifier;
+use inventory::{enhanced_id, item_id};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Armor {
+    pub id: String,
+    pub name: String,
+    pub armor_bonus: DiceRollModifier,
+    // An enhancement on top of the armor bonus, as on +1 chain mail.
+    pub magic_bonus: DiceRollModifier,
+    pub max_dexterity_bonus: Option<DiceRollModifier>,
+    // Anyone matching one of the classes or one of the races may wear the
+    // armor; with neither list set it is unrestricted.
+    pub allowed_classes: Vec<Class>,
+    pub allowed_races: Vec<Race>,
+    pub weight: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ArmorError {
+    Restricted { armor: String, class: Class, race: Race },
+}
+
+impl Armor {
+    pub fn new(name: &str, armor_bonus: DiceRollModifier) -> Self {
+        Self {
+            id: item_id(name),
+            name: name.to_string(),
+            armor_bonus,
+            magic_bonus: 0,
+            max_dexterity_bonus: None,
+            allowed_classes: Vec::new(),
+            allowed_races: Vec::new(),
+            weight: 0,
+        }
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_magic_bonus(mut self, magic_bonus: DiceRollModifier) -> Self {
+        self.id = enhanced_id(&self.id, magic_bonus);
+        self.magic_bonus = magic_bonus;
+        self
+    }
+
+    pub fn with_max_dexterity_bonus(mut self, max_dexterity_bonus: DiceRollModifier) -> Self {
+        self.max_dexterity_bonus = Some(max_dexterity_bonus);
+        self
+    }
+
+    pub fn allow_class(mut self, class: Class) -> Self {
+        self.allowed_classes.push(class);
+        self
+    }
+
+    pub fn allow_race(mut self, race: Race) -> Self {
+        self.allowed_races.push(race);
+        self
+    }
+
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn can_be_worn_by(&self, character: &Character) -> bool {
+        (self.allowed_classes.is_empty() && self.allowed_races.is_empty()) ||
+            self.allowed_classes.contains(&character.class) ||
+            self.allowed_races.contains(&character.race)
+    }
+
+    pub fn leather() -> Self {
+        Armor::new("leather armor", 2).with_weight(15)
+    }
+
+    pub fn chain_mail() -> Self {
+        Armor::new("chain mail", 5)
+            .with_max_dexterity_bonus(2)
+            .with_weight(40)
+    }
+
+    pub fn plate() -> Self {
+        Armor::new("plate", 8)
+            .with_max_dexterity_bonus(1)
+            .allow_class(Class::Fighter)
+            .allow_race(Race::Dwarf)
+            .with_weight(50)
+    }
+
+    pub fn elven_chain() -> Self {
+        Armor::new("elven chain", 5)
+            .with_max_dexterity_bonus(4)
+            .allow_race(Race::Elf)
+            .with_weight(20)
+    }
+}
+
+impl Character {
+    pub fn wear(&mut self, armor: Armor) -> Result<Option<Armor>, ArmorError> {
+        if !armor.can_be_worn_by(self) {
+            return Err(ArmorError::Restricted {
+                armor: armor.name,
+                class: self.class,
+                race: self.race,
+            });
+        }
+
+        Ok(self.armor.replace(armor))
+    }
+
+    pub fn take_off_armor(&mut self) -> Option<Armor> {
+        self.armor.take()
+    }
+
+    pub fn armor_bonus(&self) -> DiceRollModifier {
+        self.armor
+            .as_ref()
+            .map_or(0, |armor| armor.armor_bonus + armor.magic_bonus)
+    }
+
+    // Only a bonus is capped by armor or encumbrance; a dexterity penalty
+    // applies in full.
+    pub fn defense_dexterity_modifier(&self) -> DiceRollModifier {
+        let dexterity_modifier = self.dexterity_modifier();
+        let max_dexterity_bonus = self
+            .armor
+            .as_ref()
+            .and_then(|armor| armor.max_dexterity_bonus)
+            .into_iter()
+            .chain(self.encumbrance().max_dexterity_bonus())
+            .min();
+
+        match max_dexterity_bonus {
+            Some(max_dexterity_bonus) => cmp::min(dexterity_modifier, max_dexterity_bonus),
+            None => dexterity_modifier,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn armor_adds_to_armor_class() {
+        let mut character = Character::new(Class::Commoner);
+
+        assert_eq!(Ok(None), character.wear(Armor::leather()));
+        assert_eq!(12, character.armor_class());
+        assert_eq!(Ok(Some(Armor::leather())), character.wear(Armor::chain_mail()));
+        assert_eq!(15, character.armor_class());
+        assert_eq!(Some(Armor::chain_mail()), character.take_off_armor());
+        assert_eq!(10, character.armor_class());
+    }
+
+    #[test]
+    fn magic_armor_adds_its_enhancement() {
+        let mut character = Character::new(Class::Commoner);
+        character.wear(Armor::chain_mail().with_magic_bonus(2)).unwrap();
+
+        assert_eq!(7, character.armor_bonus());
+        assert_eq!(17, character.armor_class());
+    }
+
+    #[test]
+    fn heavy_armor_caps_the_dexterity_bonus() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.dexterity = 16;
+        fighter.wear(Armor::plate()).unwrap();
+        let attacker = Character::new(Class::Commoner);
+
+        let attack_command = attacker.attack(&fighter, 10);
+        assert_eq!(18, attack_command.armor_class);
+        assert_eq!(1, attack_command.defense_dexterity_modifier);
+        assert_eq!(19, attack_command.target_number());
+    }
+
+    #[test]
+    fn armor_does_not_cap_a_dexterity_penalty() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.dexterity = 6;
+        fighter.wear(Armor::plate()).unwrap();
+
+        assert_eq!(-2, fighter.defense_dexterity_modifier());
+    }
+
+    #[test]
+    fn plate_is_only_for_fighters_and_dwarves() {
+        let mut rogue = Character::new(Class::Rogue);
+        let mut dwarf = Character::new(Class::Rogue);
+        dwarf.race = Race::Dwarf;
+
+        assert_eq!(
+            Err(ArmorError::Restricted {
+                armor: "plate".to_string(),
+                class: Class::Rogue,
+                race: Race::Human,
+            }),
+            rogue.wear(Armor::plate())
+        );
+        assert_eq!(None, rogue.armor);
+        assert!(dwarf.wear(Armor::plate()).is_ok());
+    }
+
+    #[test]
+    fn elven_chain_is_only_for_elves() {
+        let mut elf = Character::new(Class::Rogue);
+        elf.race = Race::Elf;
+        let human = Character::new(Class::Rogue);
+
+        assert!(Armor::elven_chain().can_be_worn_by(&elf));
+        assert!(!Armor::elven_chain().can_be_worn_by(&human));
+    }
+}
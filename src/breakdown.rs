@@ -0,0 +1,254 @@
+use std::fmt;
+
+use character::Ability;
+use combat::{AttackCommand, AttackOutcome, DamageClamp, DiceRollModifier};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Term {
+    pub label: String,
+    pub value: i32,
+}
+
+impl Term {
+    fn new<S: Into<String>>(label: S, value: i32) -> Self {
+        Self {
+            label: label.into(),
+            value,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DamageBreakdown {
+    pub terms: Vec<Term>,
+    pub multiplier: i32,
+    pub minimum_damage: i32,
+    pub clamp: Option<DamageClamp>,
+    pub total: i32,
+}
+
+impl DamageBreakdown {
+    pub fn minimum_applied(&self) -> bool {
+        self.clamp == Some(DamageClamp::Minimum)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AttackBreakdown {
+    pub attack_terms: Vec<Term>,
+    pub attack_total: i32,
+    pub defense_terms: Vec<Term>,
+    pub target_number: i32,
+    pub outcome: AttackOutcome,
+    pub damage: Option<DamageBreakdown>,
+}
+
+fn push_modifier(terms: &mut Vec<Term>, label: &str, value: DiceRollModifier) {
+    if value != 0 {
+        terms.push(Term::new(label, value));
+    }
+}
+
+impl AttackCommand {
+    pub fn explain(&self) -> AttackBreakdown {
+        let mut attack_terms = vec![Term::new("roll", self.dice_roll as i32)];
+        push_modifier(&mut attack_terms, self.attack_ability.abbreviation(), self.attack_modifier);
+        push_modifier(&mut attack_terms, "level", self.level_modifier);
+        push_modifier(&mut attack_terms, "weapon", self.weapon_bonus);
+        push_modifier(&mut attack_terms, "range", self.range_penalty);
+        push_modifier(&mut attack_terms, "conditions", self.condition_attack_modifier);
+        push_modifier(
+            &mut attack_terms,
+            if self.off_hand { "off hand" } else { "main hand" },
+            self.two_weapon_penalty,
+        );
+        push_modifier(&mut attack_terms, "shield", self.shield_penalty);
+        push_modifier(&mut attack_terms, "not proficient", self.proficiency_penalty);
+
+        let mut defense_terms = vec![Term::new("AC", self.armor_class)];
+        push_modifier(&mut defense_terms, "DEX", self.defense_dexterity_modifier);
+        push_modifier(&mut defense_terms, "WIS", self.defense_wisdom_modifier);
+        push_modifier(&mut defense_terms, "cover", self.cover_modifier);
+        push_modifier(&mut defense_terms, "conditions", self.condition_defense_modifier);
+
+        let outcome = self.outcome();
+
+        AttackBreakdown {
+            attack_terms,
+            attack_total: self.attack_total(),
+            defense_terms,
+            target_number: self.target_number(),
+            outcome,
+            damage: outcome.damage().map(|total| self.explain_damage(total)),
+        }
+    }
+
+    fn explain_damage(&self, total: i32) -> DamageBreakdown {
+        let multiplier = self.damage_multiplier();
+
+        let ability_label = if self.off_hand {
+            format!("off-hand {}", Ability::Strength.abbreviation())
+        } else {
+            self.attack_ability.abbreviation().to_string()
+        };
+
+        let base_label = self
+            .damage_dice
+            .map_or("base".to_string(), |damage_dice| format!("from {}", damage_dice));
+
+        let mut terms = vec![Term::new(base_label, self.base_damage(multiplier))];
+        push_modifier(&mut terms, &ability_label, multiplier * self.damage_modifier);
+        push_modifier(&mut terms, "weapon", multiplier * self.weapon_bonus);
+        push_modifier(&mut terms, "holy", multiplier * self.alignment_damage_modifier);
+
+        DamageBreakdown {
+            terms,
+            multiplier,
+            minimum_damage: self.minimum_damage,
+            clamp: self.weapon_damage(multiplier).1,
+            total,
+        }
+    }
+}
+
+fn write_modifiers(f: &mut fmt::Formatter, terms: &[Term]) -> fmt::Result {
+    for term in terms {
+        write!(f, " {:+} {}", term.value, term.label)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for DamageBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.terms[0].value, self.terms[0].label)?;
+        write_modifiers(f, &self.terms[1..])?;
+        if self.multiplier != 1 {
+            write!(f, " (x{} critical)", self.multiplier)?;
+        }
+        match self.clamp {
+            Some(DamageClamp::Minimum) => write!(f, ", minimum {}", self.minimum_damage)?,
+            Some(DamageClamp::ZeroFloor) => write!(f, ", weapon damage at least 0")?,
+            None => {}
+        }
+        write!(f, " = {} damage", self.total)
+    }
+}
+
+impl fmt::Display for AttackBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.attack_terms[0].value, self.attack_terms[0].label)?;
+        write_modifiers(f, &self.attack_terms[1..])?;
+        write!(f, " = {} vs ", self.attack_total)?;
+
+        write!(f, "{} {}", self.defense_terms[0].label, self.defense_terms[0].value)?;
+        write_modifiers(f, &self.defense_terms[1..])?;
+        write!(f, " = {}: ", self.target_number)?;
+
+        match self.outcome {
+            AttackOutcome::Miss => write!(f, "miss"),
+            AttackOutcome::Fumble => write!(f, "natural 1, fumble"),
+            AttackOutcome::Hit { threat: Some(_), .. } => write!(f, "hit, critical not confirmed"),
+            AttackOutcome::Hit { .. } => write!(f, "hit"),
+            AttackOutcome::Critical { .. } => write!(f, "critical hit"),
+        }?;
+
+        match self.damage {
+            Some(ref damage) => write!(f, "\n{}", damage),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::{Alignment, Character, Class};
+    use weapons::Weapon;
+
+    #[test]
+    fn every_term_of_the_attack_roll_is_labeled() {
+        let mut attacker = Character::new(Class::Fighter);
+        attacker.strength = 14;
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.dexterity = 12;
+
+        let breakdown = attacker.attack(&attackee, 15).explain();
+
+        assert_eq!(
+            vec![Term::new("roll", 15), Term::new("STR", 2), Term::new("level", 1)],
+            breakdown.attack_terms
+        );
+        assert_eq!(vec![Term::new("AC", 10), Term::new("DEX", 1)], breakdown.defense_terms);
+        assert_eq!(
+            "15 roll +2 STR +1 level = 18 vs AC 10 +1 DEX = 11: hit\n1 base +2 STR = 3 damage",
+            breakdown.to_string()
+        );
+    }
+
+    #[test]
+    fn a_miss_has_no_damage_breakdown() {
+        let attacker = Character::new(Class::Commoner);
+        let attackee = Character::new(Class::Commoner);
+
+        let breakdown = attacker.attack(&attackee, 9).explain();
+
+        assert_eq!(None, breakdown.damage);
+        assert_eq!("9 roll = 9 vs AC 10 = 10: miss", breakdown.to_string());
+    }
+
+    #[test]
+    fn critical_damage_shows_the_multiplied_terms() {
+        let mut attacker = Character::new(Class::Paladin);
+        attacker.strength = 12;
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.alignment = Alignment::Evil;
+
+        let damage = attacker.attack(&attackee, 20).explain().damage.unwrap();
+
+        assert_eq!(3, damage.multiplier);
+        assert_eq!("1 base +3 STR +6 holy (x3 critical) = 10 damage", damage.to_string());
+    }
+
+    #[test]
+    fn weapon_dice_and_bonuses_are_labeled() {
+        let mut attacker = Character::new(Class::Fighter);
+        attacker.equip(Weapon::longsword().with_magic_bonus(1));
+        let attackee = Character::new(Class::Commoner);
+
+        let breakdown = attacker.attack(&attackee, 12).with_damage_roll(6).unwrap().explain();
+
+        assert_eq!(
+            "12 roll +1 level +1 weapon = 14 vs AC 10 = 10: hit\n6 from 1d8 +1 weapon = 7 damage",
+            breakdown.to_string()
+        );
+    }
+
+    #[test]
+    fn the_damage_breakdown_notes_the_minimum_damage() {
+        let mut attacker = Character::new(Class::Monk);
+        attacker.strength = 6;
+        let attackee = Character::new(Class::Commoner);
+
+        let damage = attacker.attack(&attackee, 12).explain().damage.unwrap();
+
+        assert!(damage.minimum_applied());
+        assert_eq!("1 base -2 STR, minimum 3 = 3 damage", damage.to_string());
+    }
+
+    #[test]
+    fn the_damage_breakdown_tells_the_zero_floor_from_the_minimum() {
+        let mut attacker = Character::new(Class::Paladin);
+        attacker.strength = 1;
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.alignment = Alignment::Evil;
+
+        let damage = attacker.attack(&attackee, 19).explain().damage.unwrap();
+
+        assert!(!damage.minimum_applied());
+        assert_eq!(Some(DamageClamp::ZeroFloor), damage.clamp);
+        assert_eq!(
+            "1 base -5 STR +2 holy, weapon damage at least 0 = 2 damage",
+            damage.to_string()
+        );
+    }
+}
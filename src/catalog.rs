@@ -0,0 +1,510 @@
+use std::collections::btree_map::{self, BTreeMap};
+
+use serde_json;
+use toml;
+
+use armor::Armor;
+use character::{Alignment, Class, Race};
+use combat::DiceRollModifier;
+use damage::DamageType;
+use dice::Dice;
+use inventory::Item;
+use magic_items::{BonusTarget, MagicItem};
+use shields::Shield;
+use weapons::{Weapon, WeaponCategory};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogFile {
+    #[serde(default)]
+    weapons: Vec<WeaponDefinition>,
+    #[serde(default)]
+    armor: Vec<ArmorDefinition>,
+    #[serde(default)]
+    shields: Vec<ShieldDefinition>,
+    #[serde(default)]
+    magic_items: Vec<MagicItemDefinition>,
+}
+
+fn default_critical_threat_range() -> u32 {
+    20
+}
+
+fn default_critical_multiplier() -> i32 {
+    2
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ClassCriticalMultiplierDefinition {
+    class: Class,
+    multiplier: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WeaponDefinition {
+    id: String,
+    name: String,
+    kind: Option<String>,
+    #[serde(default)]
+    category: WeaponCategory,
+    damage: String,
+    damage_type: DamageType,
+    #[serde(default)]
+    magic_bonus: DiceRollModifier,
+    #[serde(default = "default_critical_threat_range")]
+    critical_threat_range: u32,
+    #[serde(default = "default_critical_multiplier")]
+    critical_multiplier: i32,
+    #[serde(default)]
+    class_critical_multipliers: Vec<ClassCriticalMultiplierDefinition>,
+    #[serde(default)]
+    light: bool,
+    #[serde(default)]
+    weight: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ArmorDefinition {
+    id: String,
+    name: String,
+    armor_bonus: DiceRollModifier,
+    #[serde(default)]
+    magic_bonus: DiceRollModifier,
+    max_dexterity_bonus: Option<DiceRollModifier>,
+    #[serde(default)]
+    allowed_classes: Vec<Class>,
+    #[serde(default)]
+    allowed_races: Vec<Race>,
+    #[serde(default)]
+    weight: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShieldDefinition {
+    id: String,
+    name: String,
+    armor_bonus: DiceRollModifier,
+    #[serde(default)]
+    magic_bonus: DiceRollModifier,
+    attack_penalty: DiceRollModifier,
+    #[serde(default)]
+    weight: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MagicBonusDefinition {
+    target: BonusTarget,
+    value: DiceRollModifier,
+    against: Option<Alignment>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MagicItemDefinition {
+    id: String,
+    name: String,
+    bonuses: Vec<MagicBonusDefinition>,
+    #[serde(default)]
+    weight: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CatalogError {
+    Parse(String),
+    DuplicateId(String),
+    EmptyName { id: String },
+    InvalidDice { id: String, notation: String },
+    InvalidCriticalThreatRange { id: String, critical_threat_range: u32 },
+    InvalidCriticalMultiplier { id: String, critical_multiplier: i32 },
+    NegativeArmorBonus { id: String, armor_bonus: DiceRollModifier },
+    PositiveAttackPenalty { id: String, attack_penalty: DiceRollModifier },
+}
+
+fn validate_name(id: &str, name: &str) -> Result<(), CatalogError> {
+    if name.trim().is_empty() {
+        return Err(CatalogError::EmptyName { id: id.to_string() });
+    }
+    Ok(())
+}
+
+fn validate_critical_multiplier(id: &str, critical_multiplier: i32) -> Result<(), CatalogError> {
+    if critical_multiplier < 2 {
+        return Err(CatalogError::InvalidCriticalMultiplier {
+            id: id.to_string(),
+            critical_multiplier,
+        });
+    }
+    Ok(())
+}
+
+fn validate_armor_bonus(id: &str, armor_bonus: DiceRollModifier) -> Result<(), CatalogError> {
+    if armor_bonus < 0 {
+        return Err(CatalogError::NegativeArmorBonus {
+            id: id.to_string(),
+            armor_bonus,
+        });
+    }
+    Ok(())
+}
+
+impl WeaponDefinition {
+    fn build(self) -> Result<(String, Item), CatalogError> {
+        validate_name(&self.id, &self.name)?;
+        let damage_dice: Dice = self.damage.parse().map_err(|_| CatalogError::InvalidDice {
+            id: self.id.clone(),
+            notation: self.damage.clone(),
+        })?;
+        if !(2..=20).contains(&self.critical_threat_range) {
+            return Err(CatalogError::InvalidCriticalThreatRange {
+                id: self.id,
+                critical_threat_range: self.critical_threat_range,
+            });
+        }
+        validate_critical_multiplier(&self.id, self.critical_multiplier)?;
+
+        // The catalog id replaces the one derived from the magic bonus.
+        let mut weapon = Weapon::new(&self.name, damage_dice, self.damage_type)
+            .with_magic_bonus(self.magic_bonus)
+            .with_id(&self.id)
+            .with_category(self.category)
+            .with_critical(self.critical_threat_range, self.critical_multiplier)
+            .with_weight(self.weight);
+        if let Some(ref kind) = self.kind {
+            weapon = weapon.with_kind(kind);
+        }
+        for class_critical in self.class_critical_multipliers {
+            validate_critical_multiplier(&self.id, class_critical.multiplier)?;
+            weapon = weapon
+                .with_class_critical_multiplier(class_critical.class, class_critical.multiplier);
+        }
+        if self.light {
+            weapon = weapon.light();
+        }
+
+        Ok((self.id, Item::Weapon(weapon)))
+    }
+}
+
+impl ArmorDefinition {
+    fn build(self) -> Result<(String, Item), CatalogError> {
+        validate_name(&self.id, &self.name)?;
+        validate_armor_bonus(&self.id, self.armor_bonus)?;
+
+        let mut armor = Armor::new(&self.name, self.armor_bonus)
+            .with_magic_bonus(self.magic_bonus)
+            .with_id(&self.id)
+            .with_weight(self.weight);
+        armor.max_dexterity_bonus = self.max_dexterity_bonus;
+        armor.allowed_classes = self.allowed_classes;
+        armor.allowed_races = self.allowed_races;
+
+        Ok((self.id, Item::Armor(armor)))
+    }
+}
+
+impl ShieldDefinition {
+    fn build(self) -> Result<(String, Item), CatalogError> {
+        validate_name(&self.id, &self.name)?;
+        validate_armor_bonus(&self.id, self.armor_bonus)?;
+        if self.attack_penalty > 0 {
+            return Err(CatalogError::PositiveAttackPenalty {
+                id: self.id,
+                attack_penalty: self.attack_penalty,
+            });
+        }
+
+        let shield = Shield::new(&self.name, self.armor_bonus, self.attack_penalty)
+            .with_magic_bonus(self.magic_bonus)
+            .with_id(&self.id)
+            .with_weight(self.weight);
+
+        Ok((self.id, Item::Shield(shield)))
+    }
+}
+
+impl MagicItemDefinition {
+    fn build(self) -> Result<(String, Item), CatalogError> {
+        validate_name(&self.id, &self.name)?;
+
+        let mut item = MagicItem::new(&self.name)
+            .with_id(&self.id)
+            .with_weight(self.weight);
+        for bonus in self.bonuses {
+            item = match bonus.against {
+                Some(alignment) => item.with_bonus_against(bonus.target, bonus.value, alignment),
+                None => item.with_bonus(bonus.target, bonus.value),
+            };
+        }
+
+        Ok((self.id, Item::MagicItem(item)))
+    }
+}
+
+// Every item is stored under a stable id, which the item keeps when it is
+// handed out, so characters and saved games can refer to items without
+// embedding their stats.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ItemCatalog {
+    items: BTreeMap<String, Item>,
+}
+
+impl ItemCatalog {
+    pub fn from_json(data: &str) -> Result<Self, CatalogError> {
+        let file =
+            serde_json::from_str(data).map_err(|error| CatalogError::Parse(error.to_string()))?;
+        ItemCatalog::from_file(file)
+    }
+
+    pub fn from_toml(data: &str) -> Result<Self, CatalogError> {
+        let file = toml::from_str(data).map_err(|error| CatalogError::Parse(error.to_string()))?;
+        ItemCatalog::from_file(file)
+    }
+
+    fn from_file(file: CatalogFile) -> Result<Self, CatalogError> {
+        let mut catalog = ItemCatalog::default();
+
+        for weapon in file.weapons {
+            catalog.insert(weapon.build()?)?;
+        }
+        for armor in file.armor {
+            catalog.insert(armor.build()?)?;
+        }
+        for shield in file.shields {
+            catalog.insert(shield.build()?)?;
+        }
+        for magic_item in file.magic_items {
+            catalog.insert(magic_item.build()?)?;
+        }
+
+        Ok(catalog)
+    }
+
+    fn insert(&mut self, (id, item): (String, Item)) -> Result<(), CatalogError> {
+        if self.items.contains_key(&id) {
+            return Err(CatalogError::DuplicateId(id));
+        }
+        self.items.insert(id, item);
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Item> {
+        self.items.get(id)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.items.contains_key(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn ids(&self) -> btree_map::Keys<'_, String, Item> {
+        self.items.keys()
+    }
+
+    pub fn iter(&self) -> btree_map::Iter<'_, String, Item> {
+        self.items.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::{Ability, Character};
+
+    const JSON_CATALOG: &str = r#"{
+        "weapons": [
+            {
+                "id": "longsword",
+                "name": "longsword",
+                "category": "martial",
+                "damage": "1d8",
+                "damage_type": "slashing",
+                "critical_threat_range": 19,
+                "weight": 4
+            },
+            {
+                "id": "plus-one-longsword",
+                "name": "+1 longsword",
+                "kind": "longsword",
+                "category": "martial",
+                "damage": "1d8",
+                "damage_type": "slashing",
+                "magic_bonus": 1
+            },
+            {
+                "id": "rogue-waraxe",
+                "name": "waraxe",
+                "damage": "1d10",
+                "damage_type": "slashing",
+                "critical_multiplier": 3,
+                "class_critical_multipliers": [{ "class": "rogue", "multiplier": 4 }],
+                "magic_bonus": 2,
+                "weight": 8
+            }
+        ],
+        "armor": [
+            {
+                "id": "plate",
+                "name": "plate",
+                "armor_bonus": 8,
+                "max_dexterity_bonus": 1,
+                "allowed_classes": ["fighter"],
+                "allowed_races": ["dwarf"],
+                "weight": 50
+            }
+        ],
+        "magic_items": [
+            {
+                "id": "belt-of-giant-strength",
+                "name": "belt of giant strength",
+                "bonuses": [{ "target": { "ability_score": "strength" }, "value": 4 }],
+                "weight": 1
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn a_json_catalog_looks_items_up_by_id() {
+        let catalog = ItemCatalog::from_json(JSON_CATALOG).unwrap();
+
+        assert_eq!(5, catalog.len());
+        assert_eq!(Some(&Item::Weapon(Weapon::longsword())), catalog.get("longsword"));
+        assert_eq!(Some(&Item::Armor(Armor::plate())), catalog.get("plate"));
+        assert_eq!(
+            Some(&Item::MagicItem(MagicItem::belt_of_giant_strength(4))),
+            catalog.get("belt-of-giant-strength")
+        );
+        assert_eq!(None, catalog.get("scimitar"));
+    }
+
+    #[test]
+    fn a_renamed_catalog_weapon_keeps_its_kind() {
+        let catalog = ItemCatalog::from_json(JSON_CATALOG).unwrap();
+        let mut elf = Character::new(Class::Commoner);
+        elf.race = Race::Elf;
+
+        match catalog.get("plus-one-longsword") {
+            Some(Item::Weapon(weapon)) => {
+                assert_eq!("longsword", weapon.kind);
+                assert!(elf.is_proficient_with(weapon));
+            }
+            other => panic!("unexpected item {:?}", other),
+        }
+    }
+
+    #[test]
+    fn catalog_items_work_like_built_in_ones() {
+        let catalog = ItemCatalog::from_json(JSON_CATALOG).unwrap();
+        let mut rogue = Character::new(Class::Rogue);
+        rogue.inventory.add(catalog.get("rogue-waraxe").unwrap().clone(), 1);
+        rogue.inventory.add(catalog.get("belt-of-giant-strength").unwrap().clone(), 1);
+
+        rogue.equip_from_inventory("rogue-waraxe").unwrap();
+        rogue.equip_from_inventory("belt-of-giant-strength").unwrap();
+
+        let waraxe = rogue.main_hand.as_ref().unwrap();
+        assert_eq!("rogue-waraxe", waraxe.id);
+        assert_eq!(Some(4), waraxe.critical_multiplier_for(Class::Rogue));
+        assert_eq!(14, rogue.ability_score(Ability::Strength));
+    }
+
+    #[test]
+    fn a_toml_catalog_loads_too() {
+        let catalog = ItemCatalog::from_toml(
+            r#"
+            [[shields]]
+            id = "buckler"
+            name = "buckler"
+            armor_bonus = 1
+            attack_penalty = -1
+            weight = 5
+
+            [[magic_items]]
+            id = "ring-of-protection"
+            name = "ring of protection"
+            bonuses = [{ target = "armor_class", value = 2 }]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec!["buckler", "ring-of-protection"],
+            catalog.ids().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&Item::Shield(Shield::buckler())), catalog.get("buckler"));
+        assert_eq!(
+            Some(&Item::MagicItem(MagicItem::ring_of_protection(2))),
+            catalog.get("ring-of-protection")
+        );
+    }
+
+    #[test]
+    fn duplicate_ids_are_rejected() {
+        let result = ItemCatalog::from_toml(
+            r#"
+            [[shields]]
+            id = "buckler"
+            name = "buckler"
+            armor_bonus = 1
+            attack_penalty = -1
+
+            [[armor]]
+            id = "buckler"
+            name = "leather armor"
+            armor_bonus = 2
+            "#,
+        );
+
+        assert_eq!(Err(CatalogError::DuplicateId("buckler".to_string())), result);
+    }
+
+    #[test]
+    fn invalid_items_are_rejected_at_load_time() {
+        let result = ItemCatalog::from_json(
+            r#"{ "weapons": [{ "id": "club", "name": "club", "damage": "d6",
+                               "damage_type": "bludgeoning" }] }"#,
+        );
+        assert_eq!(
+            Err(CatalogError::InvalidDice {
+                id: "club".to_string(),
+                notation: "d6".to_string(),
+            }),
+            result
+        );
+
+        let result = ItemCatalog::from_json(
+            r#"{ "weapons": [{ "id": "club", "name": "club", "damage": "1d6",
+                               "damage_type": "bludgeoning", "critical_threat_range": 0 }] }"#,
+        );
+        assert_eq!(
+            Err(CatalogError::InvalidCriticalThreatRange {
+                id: "club".to_string(),
+                critical_threat_range: 0,
+            }),
+            result
+        );
+    }
+
+    #[test]
+    fn malformed_data_is_a_parse_error() {
+        let unknown_damage_type = ItemCatalog::from_json(
+            r#"{ "weapons": [{ "id": "club", "name": "club", "damage": "1d6",
+                               "damage_type": "psychic" }] }"#,
+        );
+        let unknown_field = ItemCatalog::from_json(r#"{ "potions": [] }"#);
+
+        assert!(matches!(unknown_damage_type, Err(CatalogError::Parse(_))));
+        assert!(matches!(unknown_field, Err(CatalogError::Parse(_))));
+    }
+}
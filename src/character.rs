@@ -1,4 +1,5 @@
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Class {
     Fighter,
     Rogue,
@@ -7,28 +8,61 @@ pub enum Class {
     Commoner,
 }
 
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Alignment {
     Good,
+    #[default]
     Neutral,
     Evil,
 }
 
-impl Default for Alignment {
-    fn default() -> Self {
-        Alignment::Neutral
-    }
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Race {
+    #[default]
     Human,
     Orc,
+    Elf,
+    Dwarf,
+}
+
+use std::cmp;
+
+use armor::Armor;
+use conditions::Conditions;
+use currency::CoinPurse;
+use damage::DamageTraits;
+use dice::{Dice, DiceRoller};
+use inventory::Inventory;
+use leveling::LevelUpObserver;
+use magic_items::{BonusTarget, MagicItem};
+use ranged::Cover;
+use shields::Shield;
+use two_weapon::TwoWeaponFighting;
+use weapons::Weapon;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ability {
+    Strength,
+    Dexterity,
+    Constitution,
+    Wisdom,
+    Intelligence,
+    Charisma,
 }
 
-impl Default for Race {
-    fn default() -> Self {
-        Race::Human
+impl Ability {
+    pub fn abbreviation(&self) -> &'static str {
+        match *self {
+            Ability::Strength => "STR",
+            Ability::Dexterity => "DEX",
+            Ability::Constitution => "CON",
+            Ability::Wisdom => "WIS",
+            Ability::Intelligence => "INT",
+            Ability::Charisma => "CHA",
+        }
     }
 }
 
@@ -37,7 +71,7 @@ pub struct Character {
     pub class: Class,
     pub alignment: Alignment,
     pub race: Race,
-    pub armor_class: i32,
+    pub base_armor_class: i32,
     pub damage: u32,
     pub strength: u32,
     pub dexterity: u32,
@@ -46,6 +80,21 @@ pub struct Character {
     pub intelligence: u32,
     pub charisma: u32,
     pub experience_points: u64,
+    pub damage_traits: DamageTraits,
+    pub cover: Cover,
+    pub conditions: Conditions,
+    pub two_weapon_fighting: Option<TwoWeaponFighting>,
+    pub main_hand: Option<Weapon>,
+    pub off_hand: Option<Weapon>,
+    pub dropped_weapon: Option<Weapon>,
+    pub armor: Option<Armor>,
+    pub shield: Option<Shield>,
+    pub magic_items: Vec<MagicItem>,
+    pub inventory: Inventory,
+    pub purse: CoinPurse,
+    pub hit_point_rolls: Vec<u32>,
+    pub(crate) hit_point_roller: Option<Box<dyn DiceRoller>>,
+    pub(crate) level_up_observers: Vec<LevelUpObserver>,
 }
 
 fn modifier_score(score: u32) -> i32 {
@@ -61,6 +110,7 @@ fn modifier_score(score: u32) -> i32 {
         16 | 17 => 3,
         18 | 19 => 4,
         20 => 5,
+        score if score > 20 => score as i32 / 2 - 5,
         _ => 0,
     }
 }
@@ -69,10 +119,10 @@ impl Character {
     pub fn new(class: Class) -> Self {
         Self {
             name: String::new(),
-            class: class,
+            class,
             alignment: Alignment::default(),
             race: Race::default(),
-            armor_class: 10,
+            base_armor_class: 10,
             damage: 0,
             strength: 10,
             dexterity: 10,
@@ -81,18 +131,114 @@ impl Character {
             intelligence: 10,
             charisma: 10,
             experience_points: 0,
+            damage_traits: DamageTraits::default(),
+            cover: Cover::default(),
+            conditions: Conditions::default(),
+            two_weapon_fighting: None,
+            main_hand: None,
+            off_hand: None,
+            dropped_weapon: None,
+            armor: None,
+            shield: None,
+            magic_items: Vec::new(),
+            inventory: Inventory::default(),
+            purse: CoinPurse::default(),
+            hit_point_rolls: Vec::new(),
+            hit_point_roller: None,
+            level_up_observers: Vec::new(),
         }
     }
 
-    pub fn max_hit_points(&self) -> u32 {
-        let hit_points_per_level = match self.class {
+    pub fn hit_die(&self) -> Dice {
+        match self.class {
+            Class::Fighter => Dice::new(1, 10),
+            Class::Monk => Dice::new(1, 6),
+            Class::Paladin => Dice::new(1, 8),
+            _ => Dice::new(1, 4),
+        }
+    }
+
+    // Taken on every level-up unless the character rolls the hit die instead.
+    pub fn hit_points_per_level(&self) -> u32 {
+        match self.class {
             Class::Fighter => 10,
             Class::Monk => 6,
             Class::Paladin => 8,
             _ => 5,
-        };
+        }
+    }
+
+    pub fn rolls_hit_points(&self) -> bool {
+        self.hit_point_roller.is_some()
+    }
 
-        10 + ((self.level() as i32 - 1) * hit_points_per_level) as u32
+    // Opts into rolling the hit die on every level-up instead of taking the
+    // fixed per-level value. Levels gained before opting in keep the fixed value.
+    pub fn roll_hit_points_with<R>(&mut self, roller: R)
+    where
+        R: DiceRoller + 'static,
+    {
+        let levels_gained = (self.level() - 1) as usize;
+        let hit_points_per_level = self.hit_points_per_level();
+
+        while self.hit_point_rolls.len() < levels_gained {
+            self.hit_point_rolls.push(hit_points_per_level);
+        }
+        self.hit_point_roller = Some(Box::new(roller));
+    }
+
+    pub(crate) fn record_hit_point_rolls(&mut self) {
+        let levels_gained = (self.level() - 1) as usize;
+        let hit_die = self.hit_die();
+
+        if let Some(ref mut roller) = self.hit_point_roller {
+            while self.hit_point_rolls.len() < levels_gained {
+                self.hit_point_rolls.push(hit_die.roll(roller.as_mut()));
+            }
+        }
+    }
+
+    // Every level adds the constitution modifier, whatever raised it, but
+    // never less than 1 hit point. Levels without a recorded roll take the
+    // fixed per-level value.
+    pub fn max_hit_points(&self) -> u32 {
+        let constitution_modifier = self.constitution_modifier();
+        let hit_points_per_level = self.hit_points_per_level();
+        let first_level = cmp::max(1, 10 + constitution_modifier);
+
+        let later_levels: i32 = (0..(self.level() - 1) as usize)
+            .map(|index| {
+                let roll = *self.hit_point_rolls.get(index).unwrap_or(&hit_points_per_level);
+                cmp::max(1, roll as i32 + constitution_modifier)
+            })
+            .sum();
+
+        (first_level + later_levels) as u32
+    }
+
+    pub fn armor_class(&self) -> i32 {
+        self.armor_class_against(None)
+    }
+
+    // Some magic items only protect against opponents of a given alignment.
+    pub fn armor_class_against(&self, opponent: Option<Alignment>) -> i32 {
+        self.base_armor_class + self.armor_bonus() + self.shield_bonus() +
+            self.magic_bonus(BonusTarget::ArmorClass, opponent) + match self.race {
+                Race::Orc => 2,
+                _ => 0,
+            }
+    }
+
+    pub fn ability_score(&self, ability: Ability) -> u32 {
+        let score = match ability {
+            Ability::Strength => self.strength,
+            Ability::Dexterity => self.dexterity,
+            Ability::Constitution => self.constitution,
+            Ability::Wisdom => self.wisdom,
+            Ability::Intelligence => self.intelligence,
+            Ability::Charisma => self.charisma,
+        };
+        score + self.magic_bonus(BonusTarget::AbilityScore(ability), None) as u32
     }
 
     pub fn is_dead(&self) -> bool {
@@ -104,51 +250,52 @@ impl Character {
     }
 
     pub fn strength_modifier(&self) -> i32 {
-        modifier_score(self.strength) + match self.race {
+        modifier_score(self.ability_score(Ability::Strength)) + match self.race {
             Race::Orc => 2,
             _ => 0,
         }
     }
 
     pub fn intelligence_modifier(&self) -> i32 {
-        modifier_score(self.intelligence) + match self.race {
+        modifier_score(self.ability_score(Ability::Intelligence)) + match self.race {
             Race::Orc => -1,
             _ => 0,
         }
     }
 
     pub fn wisdom_modifier(&self) -> i32 {
-        modifier_score(self.wisdom) + match self.race {
+        modifier_score(self.ability_score(Ability::Wisdom)) + match self.race {
             Race::Orc => -1,
             _ => 0,
         }
     }
 
     pub fn charisma_modifier(&self) -> i32 {
-        modifier_score(self.charisma) + match self.race {
+        modifier_score(self.ability_score(Ability::Charisma)) + match self.race {
             Race::Orc => -1,
             _ => 0,
         }
     }
 
     pub fn dexterity_modifier(&self) -> i32 {
-        modifier_score(self.dexterity)
+        modifier_score(self.ability_score(Ability::Dexterity))
     }
 
     pub fn constitution_modifier(&self) -> i32 {
-        modifier_score(self.constitution)
+        modifier_score(self.ability_score(Ability::Constitution))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use dice::LoadedDice;
 
     #[test]
     fn it_defaults_to_10_ac_5_hp() {
         let character = Character::new(Class::Commoner);
 
-        assert_eq!(10, character.armor_class);
+        assert_eq!(10, character.armor_class());
         assert_eq!(0, character.damage);
     }
 
@@ -266,6 +413,62 @@ mod tests {
     }
 
 
+    #[test]
+    fn each_class_rolls_a_single_hit_die() {
+        assert_eq!(Dice::new(1, 10), Character::new(Class::Fighter).hit_die());
+        assert_eq!(Dice::new(1, 8), Character::new(Class::Paladin).hit_die());
+        assert_eq!(Dice::new(1, 6), Character::new(Class::Monk).hit_die());
+        assert_eq!(Dice::new(1, 4), Character::new(Class::Commoner).hit_die());
+    }
+
+    #[test]
+    fn constitution_adds_hit_points_per_level_but_never_takes_a_level_away() {
+        let mut character = Character::new(Class::Commoner);
+        character.experience_points = 1000;
+
+        character.constitution = 14;
+        assert_eq!(19, character.max_hit_points());
+
+        character.constitution = 1;
+        assert_eq!(6, character.max_hit_points());
+    }
+
+    #[test]
+    fn constitution_adds_rolled_hit_points_per_level_but_never_takes_a_level_away() {
+        let mut character = Character::new(Class::Commoner);
+        character.roll_hit_points_with(LoadedDice::new(vec![3]).unwrap());
+        character.gain_experience(1000);
+
+        character.constitution = 14;
+        assert_eq!(17, character.max_hit_points());
+
+        character.constitution = 1;
+        assert_eq!(6, character.max_hit_points());
+    }
+
+    #[test]
+    fn rolled_hit_points_are_recorded_on_level_up() {
+        let mut character = Character::new(Class::Fighter);
+        character.roll_hit_points_with(LoadedDice::new(vec![3, 7]).unwrap());
+
+        character.gain_experience(2000);
+
+        assert_eq!(vec![3, 7], character.hit_point_rolls);
+        assert_eq!(20, character.max_hit_points());
+    }
+
+    #[test]
+    fn unrolled_levels_fall_back_to_the_fixed_hit_points() {
+        let mut character = Character::new(Class::Fighter);
+        character.experience_points = 1000;
+        character.roll_hit_points_with(LoadedDice::new(vec![2]).unwrap());
+
+        character.gain_experience(1000);
+
+        assert_eq!(vec![10, 2], character.hit_point_rolls);
+        assert_eq!(22, character.max_hit_points());
+    }
+
     #[test]
     fn as_an_orc_my_armor_class_is_two_better() {
         let mut orc = Character::new(Class::Commoner);
@@ -1,18 +1,109 @@
 use std::cmp;
 
-use character::{Character, Class, Alignment};
+use advantage::{AdvantageSource, RollMode};
+use character::{Ability, Character, Class, Alignment, Race};
+use damage::{DamagePacket, DamageType};
+use dice::{Dice, DiceRoller};
+use leveling::LevelUpReport;
+use weapons::Weapon;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CombatEvent {
+    pub attacker: String,
+    pub defender: String,
+    pub dice_roll: u32,
+    pub raw_dice_rolls: Vec<u32>,
+    pub attack_total: i32,
+    pub target_number: i32,
+    pub outcome: AttackOutcome,
+    pub damage_dealt: u32,
+    pub experience_points: u64,
+    pub level_up: Option<LevelUpReport>,
+    pub defender_damage: u32,
+    pub defender_max_hit_points: u32,
+    pub defender_is_dead: bool,
+    pub killing_blow: bool,
+}
 
-pub fn resolve_combat(command: &AttackCommand, attacker: &mut Character, attackee: &mut Character) {
-    attackee.damage += command.damage().unwrap_or(0) as u32;
-    attacker.experience_points += command.experience_points();
+pub fn resolve_combat(
+    command: &AttackCommand,
+    attacker: &mut Character,
+    attackee: &mut Character,
+) -> CombatEvent {
+    let damage_dealt = attackee.take_damage(&command.damage_packets());
+    let killing_blow = !command.attackee_is_dead && attackee.is_dead();
+
+    let mut experience_points = command.experience_points();
+    if killing_blow {
+        experience_points += command.kill_experience_points();
+    }
+
+    CombatEvent {
+        attacker: attacker.name.clone(),
+        defender: attackee.name.clone(),
+        dice_roll: command.dice_roll,
+        raw_dice_rolls: command.raw_dice_rolls.clone(),
+        attack_total: command.attack_total(),
+        target_number: command.target_number(),
+        outcome: command.outcome(),
+        damage_dealt,
+        experience_points,
+        level_up: attacker.gain_experience(experience_points),
+        defender_damage: attackee.damage,
+        defender_max_hit_points: attackee.max_hit_points(),
+        defender_is_dead: attackee.is_dead(),
+        killing_blow,
+    }
 }
 
 pub type DiceRollModifier = i32;
 
+// A damage roll the attack's damage dice cannot produce.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DamageRollError {
+    pub damage_roll: u32,
+    pub damage_dice: Option<Dice>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CriticalThreat {
+    pub dice_roll: u32,
+    pub confirmation_roll: Option<u32>,
+    pub confirmed: bool,
+}
+
+// Which rule, if any, raised the weapon's share of the damage.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DamageClamp {
+    ZeroFloor,
+    Minimum,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AttackOutcome {
+    Miss,
+    Fumble,
+    Hit { damage: i32, threat: Option<CriticalThreat> },
+    Critical { damage: i32, threat: CriticalThreat },
+}
+
+impl AttackOutcome {
+    pub fn damage(&self) -> Option<i32> {
+        match *self {
+            AttackOutcome::Hit { damage, .. } | AttackOutcome::Critical { damage, .. } => {
+                Some(damage)
+            }
+            AttackOutcome::Miss | AttackOutcome::Fumble => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct AttackCommand {
     pub dice_roll: u32,
+    pub attack_ability: Ability,
     pub attack_modifier: DiceRollModifier,
+    pub damage_modifier: DiceRollModifier,
     pub level_modifier: DiceRollModifier,
     pub defense_dexterity_modifier: DiceRollModifier,
     pub constitution_modifier: DiceRollModifier,
@@ -21,55 +112,302 @@ pub struct AttackCommand {
     pub critical_hit_multiplier: i32,
     pub minimum_damage: i32,
     pub alignment_damage_modifier: DiceRollModifier,
+    pub attacker_level: u64,
+    pub attackee_level: u64,
+    pub attackee_is_dead: bool,
+    pub critical_threat_range: u32,
+    pub confirmation_roll: Option<u32>,
+    pub damage_type: DamageType,
+    pub roll_mode: RollMode,
+    pub raw_dice_rolls: Vec<u32>,
+    pub range_penalty: DiceRollModifier,
+    pub cover_modifier: DiceRollModifier,
+    pub condition_attack_modifier: DiceRollModifier,
+    pub condition_defense_modifier: DiceRollModifier,
+    pub automatic_critical: bool,
+    // Attacks such as thrown splash weapons never threaten a critical hit.
+    pub can_critical_hit: bool,
+    pub two_weapon_penalty: DiceRollModifier,
+    pub shield_penalty: DiceRollModifier,
+    pub proficiency_penalty: DiceRollModifier,
+    pub off_hand: bool,
+    pub damage_dice: Option<Dice>,
+    pub damage_roll: Option<u32>,
+    pub weapon_bonus: DiceRollModifier,
+}
+
+impl Default for AttackCommand {
+    fn default() -> Self {
+        Self {
+            dice_roll: 0,
+            attack_ability: Ability::Strength,
+            attack_modifier: 0,
+            damage_modifier: 0,
+            level_modifier: 0,
+            defense_dexterity_modifier: 0,
+            constitution_modifier: 0,
+            defense_wisdom_modifier: 0,
+            armor_class: 10,
+            critical_hit_multiplier: 2,
+            minimum_damage: 1,
+            alignment_damage_modifier: 0,
+            attacker_level: 1,
+            attackee_level: 1,
+            attackee_is_dead: false,
+            critical_threat_range: 20,
+            confirmation_roll: None,
+            damage_type: DamageType::default(),
+            roll_mode: RollMode::Normal,
+            raw_dice_rolls: Vec::new(),
+            range_penalty: 0,
+            cover_modifier: 0,
+            condition_attack_modifier: 0,
+            condition_defense_modifier: 0,
+            automatic_critical: false,
+            can_critical_hit: true,
+            two_weapon_penalty: 0,
+            shield_penalty: 0,
+            proficiency_penalty: 0,
+            off_hand: false,
+            damage_dice: None,
+            damage_roll: None,
+            weapon_bonus: 0,
+        }
+    }
+}
+
+fn scaled_experience_points(base: i64, attacker_level: u64, attackee_level: u64) -> u64 {
+    let level_difference = attackee_level as i64 - attacker_level as i64;
+    cmp::max(1, base + base / 2 * level_difference) as u64
 }
 
 impl AttackCommand {
+    // Keeps one of two d20s according to the roll mode. Both dice stay on the
+    // command.
+    pub fn with_rolls(self, dice_rolls: (u32, u32), roll_mode: RollMode) -> Self {
+        let (first, second) = dice_rolls;
+
+        AttackCommand {
+            dice_roll: roll_mode.keep(first, second),
+            roll_mode,
+            raw_dice_rolls: vec![first, second],
+            ..self
+        }
+    }
+
+    pub fn with_confirmation_roll(mut self, confirmation_roll: u32) -> Self {
+        self.confirmation_roll = Some(confirmation_roll);
+        self
+    }
+
+    pub fn with_damage_roll(mut self, damage_roll: u32) -> Result<Self, DamageRollError> {
+        match self.damage_dice {
+            Some(damage_dice)
+                if (damage_dice.minimum()..=damage_dice.maximum()).contains(&damage_roll) =>
+            {
+                self.damage_roll = Some(damage_roll);
+                Ok(self)
+            }
+            damage_dice => Err(DamageRollError {
+                damage_roll,
+                damage_dice,
+            }),
+        }
+    }
+
+    pub fn roll_damage<R>(mut self, roller: &mut R) -> Self
+    where
+        R: DiceRoller + ?Sized,
+    {
+        self.damage_roll = self.damage_dice.map(|damage_dice| damage_dice.roll(roller));
+        self
+    }
+
+    fn total_for(&self, dice_roll: u32) -> i32 {
+        dice_roll as i32 + self.attack_modifier + self.level_modifier + self.range_penalty +
+            self.condition_attack_modifier + self.two_weapon_penalty + self.shield_penalty +
+            self.proficiency_penalty + self.weapon_bonus
+    }
+
+    fn hits(&self, dice_roll: u32) -> bool {
+        match dice_roll {
+            1 => false,
+            20 => true,
+            _ => self.total_for(dice_roll) >= self.target_number(),
+        }
+    }
+
+    pub fn attack_total(&self) -> i32 {
+        self.total_for(self.dice_roll)
+    }
+
+    pub fn target_number(&self) -> i32 {
+        self.defense_dexterity_modifier + self.armor_class + self.defense_wisdom_modifier +
+            self.cover_modifier + self.condition_defense_modifier
+    }
+
     pub fn succeeds(&self) -> bool {
-        (self.dice_roll as i32 + self.attack_modifier + self.level_modifier) >=
-            (self.defense_dexterity_modifier + self.armor_class + self.defense_wisdom_modifier)
+        self.hits(self.dice_roll)
+    }
+
+    pub fn is_threat(&self) -> bool {
+        self.can_critical_hit && self.succeeds() &&
+            (self.automatic_critical || self.dice_roll >= self.critical_threat_range)
+    }
+
+    // Without a confirmation roll every threat is an automatic critical hit,
+    // as is any hit against a helpless defender.
+    pub fn critical_threat(&self) -> Option<CriticalThreat> {
+        if !self.is_threat() {
+            return None;
+        }
+
+        Some(CriticalThreat {
+            dice_roll: self.dice_roll,
+            confirmation_roll: self.confirmation_roll,
+            confirmed: self.automatic_critical ||
+                self.confirmation_roll.is_none_or(|roll| self.hits(roll)),
+        })
     }
 
     pub fn is_critical(&self) -> bool {
-        self.dice_roll == 20
+        self.critical_threat().is_some_and(|threat| threat.confirmed)
     }
 
-    pub fn damage(&self) -> Option<i32> {
-        let additional_dmg = self.attack_modifier + self.alignment_damage_modifier;
-        if !self.succeeds() {
-            None
-        } else if self.is_critical() {
-            Some(cmp::max(
-                (self.critical_hit_multiplier * additional_dmg + 1),
-                self.minimum_damage,
-            ))
+    // An unarmed strike deals a flat 1 that critical hits do not multiply. A
+    // weapon's dice use the damage roll when one was made and the dice's
+    // average otherwise.
+    pub fn base_damage(&self, multiplier: i32) -> i32 {
+        match self.damage_dice {
+            Some(damage_dice) => {
+                multiplier * self.damage_roll.unwrap_or_else(|| damage_dice.average()) as i32
+            }
+            None => 1,
+        }
+    }
+
+    pub fn damage_multiplier(&self) -> i32 {
+        if self.is_critical() {
+            self.critical_hit_multiplier
         } else {
-            Some(cmp::max((1 + additional_dmg), self.minimum_damage))
+            1
         }
     }
 
+    // The weapon's share of the damage is never negative and makes up any
+    // shortfall of the total against the minimum damage.
+    pub fn weapon_damage(&self, multiplier: i32) -> (i32, Option<DamageClamp>) {
+        let holy_damage = multiplier * self.alignment_damage_modifier;
+        let weapon_damage =
+            self.base_damage(multiplier) + multiplier * (self.damage_modifier + self.weapon_bonus);
+
+        let minimum = self.minimum_damage - holy_damage;
+
+        if weapon_damage >= cmp::max(minimum, 0) {
+            (weapon_damage, None)
+        } else if minimum > 0 {
+            (minimum, Some(DamageClamp::Minimum))
+        } else {
+            (0, Some(DamageClamp::ZeroFloor))
+        }
+    }
+
+    // The weapon's share of the damage carries the attack's damage type; the
+    // alignment bonus is dealt as holy damage.
+    pub fn damage_packets(&self) -> Vec<DamagePacket> {
+        if !self.succeeds() {
+            return Vec::new();
+        }
+
+        let multiplier = self.damage_multiplier();
+        let holy_damage = multiplier * self.alignment_damage_modifier;
+        let (weapon_damage, _) = self.weapon_damage(multiplier);
+
+        let mut packets = vec![DamagePacket::new(weapon_damage, self.damage_type)];
+        if holy_damage != 0 {
+            packets.push(DamagePacket::new(holy_damage, DamageType::Holy));
+        }
+        packets
+    }
+
+    pub fn outcome(&self) -> AttackOutcome {
+        if self.dice_roll == 1 {
+            return AttackOutcome::Fumble;
+        } else if !self.succeeds() {
+            return AttackOutcome::Miss;
+        }
+
+        let damage = self.damage_packets().iter().map(|packet| packet.amount).sum();
+        match self.critical_threat() {
+            Some(threat) if threat.confirmed => AttackOutcome::Critical { damage, threat },
+            threat => AttackOutcome::Hit { damage, threat },
+        }
+    }
+
+    pub fn damage(&self) -> Option<i32> {
+        self.outcome().damage()
+    }
+
     pub fn experience_points(&self) -> u64 {
-        if self.succeeds() { 10 } else { 0 }
+        if self.attackee_is_dead || !self.succeeds() {
+            0
+        } else {
+            scaled_experience_points(10, self.attacker_level, self.attackee_level)
+        }
+    }
+
+    pub fn kill_experience_points(&self) -> u64 {
+        if self.attackee_is_dead {
+            0
+        } else {
+            scaled_experience_points(50, self.attacker_level, self.attackee_level)
+        }
     }
 }
 
 impl Character {
     pub fn attack(&self, attackee: &Character, dice_roll: u32) -> AttackCommand {
+        self.attack_with_weapon(attackee, dice_roll, self.main_hand.as_ref())
+    }
+
+    pub(crate) fn attack_with_weapon(
+        &self,
+        attackee: &Character,
+        dice_roll: u32,
+        weapon: Option<&Weapon>,
+    ) -> AttackCommand {
         let attackee_defense_dexterity_modifier = match self.class {
-            Class::Rogue => cmp::min(attackee.dexterity_modifier(), 0),
-            _ => attackee.dexterity_modifier(),
+            _ if attackee.conditions.loses_dexterity_bonus() => {
+                cmp::min(attackee.defense_dexterity_modifier(), 0)
+            }
+            Class::Rogue => cmp::min(attackee.defense_dexterity_modifier(), 0),
+            _ => attackee.defense_dexterity_modifier(),
         };
 
-        let attack_modifier = match self.class {
-            Class::Rogue => self.dexterity_modifier(),
-            _ => self.strength_modifier(),
+        let (attack_ability, attack_modifier) = match self.class {
+            Class::Rogue => (Ability::Dexterity, self.dexterity_modifier()),
+            _ => (Ability::Strength, self.strength_modifier()),
         };
 
-        let critical_hit_multiplier = match self.class {
+        let class_critical_hit_multiplier = match self.class {
             Class::Rogue => 3,
             Class::Paladin if attackee.alignment == Alignment::Evil => 3,
             _ => 2,
         };
 
+        let critical_hit_multiplier = match weapon {
+            Some(weapon) => weapon.critical_multiplier_for(self.class).unwrap_or_else(|| {
+                cmp::max(weapon.critical_multiplier, class_critical_hit_multiplier)
+            }),
+            None => class_critical_hit_multiplier,
+        };
+
+        let weapon_critical_threat_range = weapon.map_or(20, |weapon| weapon.critical_threat_range);
+        let critical_threat_range = match self.race {
+            Race::Elf => weapon_critical_threat_range - 1,
+            _ => weapon_critical_threat_range,
+        };
+
         let minimum_damage = match self.class {
             Class::Monk => 3,
             _ => 1,
@@ -90,18 +428,55 @@ impl Character {
         AttackCommand {
             dice_roll,
             level_modifier: self.level_modifier(),
-            attack_modifier: attack_modifier,
+            attack_ability,
+            attack_modifier,
+            damage_modifier: attack_modifier,
             defense_dexterity_modifier: attackee_defense_dexterity_modifier,
             constitution_modifier: attackee.constitution_modifier(),
             defense_wisdom_modifier,
-            armor_class: attackee.armor_class,
+            armor_class: attackee.armor_class_against(Some(self.alignment)),
             critical_hit_multiplier,
             minimum_damage,
             alignment_damage_modifier,
+            attacker_level: self.level(),
+            attackee_level: attackee.level(),
+            attackee_is_dead: attackee.is_dead(),
+            critical_threat_range,
+            confirmation_roll: None,
+            damage_type: weapon.map_or(DamageType::default(), |weapon| weapon.damage_type),
+            roll_mode: RollMode::Normal,
+            raw_dice_rolls: vec![dice_roll],
+            range_penalty: 0,
+            cover_modifier: attackee.cover.armor_class_bonus(),
+            condition_attack_modifier: self.conditions.attack_modifier(),
+            condition_defense_modifier: attackee.conditions.armor_class_modifier(),
+            automatic_critical: attackee.conditions.grants_automatic_critical(),
+            can_critical_hit: true,
+            two_weapon_penalty: self
+                .effective_two_weapon_fighting()
+                .map_or(0, |two_weapon_fighting| two_weapon_fighting.main_hand_attack_penalty()),
+            shield_penalty: self.shield_attack_penalty(),
+            proficiency_penalty: self.proficiency_penalty(weapon),
+            off_hand: false,
+            damage_dice: weapon.map(|weapon| self.weapon_damage_dice(weapon)),
+            damage_roll: None,
+            weapon_bonus: weapon.map_or(0, |weapon| weapon.magic_bonus),
         }
     }
 
-    fn level_modifier(&self) -> DiceRollModifier {
+    // Rolls two d20s; the sources decide whether the higher, the lower or
+    // simply the first die is kept.
+    pub fn attack_with_rolls(
+        &self,
+        attackee: &Character,
+        dice_rolls: (u32, u32),
+        sources: &[AdvantageSource],
+    ) -> AttackCommand {
+        self.attack(attackee, dice_rolls.0)
+            .with_rolls(dice_rolls, self.roll_mode_against(attackee, sources))
+    }
+
+    pub fn level_modifier(&self) -> DiceRollModifier {
         match self.class {
             Class::Fighter | Class::Paladin => self.level() as DiceRollModifier,
             Class::Monk => (self.level() * 2 / 3) as DiceRollModifier,
@@ -113,6 +488,7 @@ impl Character {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use conditions::Condition;
 
     #[test]
     fn a_player_can_successfully_attack_another_player() {
@@ -206,22 +582,309 @@ mod tests {
         assert_eq!(10, attacker.experience_points);
     }
 
+    #[test]
+    fn experience_scales_with_the_level_difference() {
+        let mut attacker = Character::new(Class::Fighter);
+        let mut attackee = Character::new(Class::Fighter);
+
+        attackee.set_level(3);
+        assert_eq!(20, attacker.attack(&attackee, 19).experience_points());
+
+        attacker.set_level(4);
+        attackee.set_level(1);
+        assert_eq!(1, attacker.attack(&attackee, 19).experience_points());
+    }
+
+    #[test]
+    fn hitting_a_dead_character_grants_no_experience() {
+        let attacker = Character::new(Class::Commoner);
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.damage = 10;
+
+        let attack_command = attacker.attack(&attackee, 15);
+        assert!(attack_command.succeeds());
+        assert_eq!(0, attack_command.experience_points());
+        assert_eq!(0, attack_command.kill_experience_points());
+    }
+
+    #[test]
+    fn the_killing_blow_awards_bonus_experience() {
+        let mut attacker = Character::new(Class::Commoner);
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.damage = 9;
+
+        resolve_combat(
+            &attacker.attack(&attackee, 15),
+            &mut attacker,
+            &mut attackee,
+        );
+        assert!(attackee.is_dead());
+        assert_eq!(60, attacker.experience_points);
+
+        resolve_combat(
+            &attacker.attack(&attackee, 15),
+            &mut attacker,
+            &mut attackee,
+        );
+        assert_eq!(60, attacker.experience_points);
+    }
+
+    #[test]
+    fn resolving_combat_describes_what_happened() {
+        let mut attacker = Character::new(Class::Commoner);
+        attacker.name = String::from("Aragorn");
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.name = String::from("Goblin");
+        attackee.damage = 9;
+
+        let event = resolve_combat(
+            &attacker.attack(&attackee, 12),
+            &mut attacker,
+            &mut attackee,
+        );
+
+        assert_eq!("Aragorn", event.attacker);
+        assert_eq!("Goblin", event.defender);
+        assert_eq!(12, event.dice_roll);
+        assert_eq!(12, event.attack_total);
+        assert_eq!(10, event.target_number);
+        assert_eq!(AttackOutcome::Hit { damage: 1, threat: None }, event.outcome);
+        assert_eq!(1, event.damage_dealt);
+        assert_eq!(60, event.experience_points);
+        assert_eq!(10, event.defender_damage);
+        assert_eq!(10, event.defender_max_hit_points);
+        assert!(event.defender_is_dead);
+        assert!(event.killing_blow);
+    }
+
+    #[test]
+    fn resolving_combat_reports_a_level_up() {
+        let mut attacker = Character::new(Class::Commoner);
+        attacker.experience_points = 990;
+        let mut attackee = Character::new(Class::Commoner);
+
+        let event = resolve_combat(
+            &attacker.attack(&attackee, 15),
+            &mut attacker,
+            &mut attackee,
+        );
+        assert_eq!(Some(2), event.level_up.map(|report| report.new_level));
+    }
+
+    #[test]
+    fn a_natural_20_always_hits_critically() {
+        let attacker = Character::new(Class::Commoner);
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.base_armor_class = 40;
+
+        let attack_command = attacker.attack(&attackee, 20);
+        assert_eq!(
+            AttackOutcome::Critical {
+                damage: 1,
+                threat: CriticalThreat {
+                    dice_roll: 20,
+                    confirmation_roll: None,
+                    confirmed: true,
+                },
+            },
+            attack_command.outcome()
+        );
+    }
+
+    #[test]
+    fn an_elf_threatens_a_critical_on_19_or_20() {
+        let mut attacker = Character::new(Class::Commoner);
+        attacker.race = Race::Elf;
+        attacker.strength = 14;
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = attacker.attack(&attackee, 19);
+        assert_eq!(19, attack_command.critical_threat_range);
+        assert_eq!(Some(5), attack_command.damage());
+        assert!(!attacker.attack(&attackee, 18).is_threat());
+    }
+
+    #[test]
+    fn a_widened_threat_range_still_needs_to_hit() {
+        let attack_command = AttackCommand {
+            dice_roll: 18,
+            armor_class: 19,
+            critical_threat_range: 18,
+            ..AttackCommand::default()
+        };
+
+        assert!(!attack_command.is_threat());
+        assert_eq!(AttackOutcome::Miss, attack_command.outcome());
+    }
+
+    #[test]
+    fn a_confirmed_threat_applies_the_critical_multiplier() {
+        let mut attacker = Character::new(Class::Commoner);
+        attacker.strength = 14;
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = attacker.attack(&attackee, 20).with_confirmation_roll(8);
+        assert_eq!(
+            AttackOutcome::Critical {
+                damage: 5,
+                threat: CriticalThreat {
+                    dice_roll: 20,
+                    confirmation_roll: Some(8),
+                    confirmed: true,
+                },
+            },
+            attack_command.outcome()
+        );
+    }
+
+    #[test]
+    fn a_failed_confirmation_is_a_normal_hit() {
+        let mut attacker = Character::new(Class::Commoner);
+        attacker.strength = 14;
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = attacker.attack(&attackee, 20).with_confirmation_roll(7);
+        assert!(!attack_command.is_critical());
+        assert_eq!(
+            AttackOutcome::Hit {
+                damage: 3,
+                threat: Some(CriticalThreat {
+                    dice_roll: 20,
+                    confirmation_roll: Some(7),
+                    confirmed: false,
+                }),
+            },
+            attack_command.outcome()
+        );
+    }
+
+    #[test]
+    fn a_natural_1_always_fumbles() {
+        let mut attacker = Character::new(Class::Fighter);
+        attacker.set_level(10);
+        attacker.strength = 20;
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = attacker.attack(&attackee, 1);
+        assert!(!attack_command.succeeds());
+        assert_eq!(AttackOutcome::Fumble, attack_command.outcome());
+    }
+
+    #[test]
+    fn a_low_roll_misses() {
+        let attacker = Character::new(Class::Commoner);
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = attacker.attack(&attackee, 9);
+        assert_eq!(AttackOutcome::Miss, attack_command.outcome());
+        assert_eq!(None, attack_command.outcome().damage());
+    }
+
+    #[test]
+    fn a_hidden_attacker_keeps_the_higher_die() {
+        let attacker = Character::new(Class::Commoner);
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command =
+            attacker.attack_with_rolls(&attackee, (4, 16), &[AdvantageSource::HiddenAttacker]);
+        assert_eq!(RollMode::Advantage, attack_command.roll_mode);
+        assert_eq!(16, attack_command.dice_roll);
+        assert_eq!(vec![4, 16], attack_command.raw_dice_rolls);
+        assert!(attack_command.succeeds());
+    }
+
+    #[test]
+    fn a_blinded_attacker_keeps_the_lower_die() {
+        let attacker = Character::new(Class::Commoner);
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command =
+            attacker.attack_with_rolls(&attackee, (20, 1), &[AdvantageSource::BlindedAttacker]);
+        assert_eq!(AttackOutcome::Fumble, attack_command.outcome());
+        assert_eq!(vec![20, 1], attack_command.raw_dice_rolls);
+    }
+
+    #[test]
+    fn cancelled_advantage_keeps_the_first_die() {
+        let attacker = Character::new(Class::Commoner);
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = attacker.attack_with_rolls(
+            &attackee,
+            (8, 18),
+            &[AdvantageSource::ProneTarget, AdvantageSource::ProneAttacker],
+        );
+        assert_eq!(RollMode::Normal, attack_command.roll_mode);
+        assert_eq!(8, attack_command.dice_roll);
+    }
+
+    #[test]
+    fn a_prone_condition_is_not_counted_twice() {
+        let attacker = Character::new(Class::Commoner);
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.conditions.add(Condition::Prone);
+
+        let attack_command =
+            attacker.attack_with_rolls(&attackee, (4, 16), &[AdvantageSource::ProneTarget]);
+        assert_eq!(RollMode::Normal, attack_command.roll_mode);
+        assert_eq!(4, attack_command.dice_roll);
+        assert_eq!(-4, attack_command.condition_defense_modifier);
+    }
+
+    #[test]
+    fn a_poisoned_attacker_has_an_attack_penalty() {
+        let mut attacker = Character::new(Class::Commoner);
+        attacker.conditions.add(Condition::Poisoned);
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = attacker.attack(&attackee, 11);
+        assert_eq!(-2, attack_command.condition_attack_modifier);
+        assert!(!attack_command.succeeds());
+    }
+
+    #[test]
+    fn a_stunned_defender_loses_its_dexterity_bonus() {
+        let attacker = Character::new(Class::Commoner);
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.dexterity = 16;
+        attackee.conditions.add_for(Condition::Stunned, 1);
+
+        let attack_command = attacker.attack(&attackee, 8);
+        assert_eq!(0, attack_command.defense_dexterity_modifier);
+        assert_eq!(-2, attack_command.condition_defense_modifier);
+        assert!(attack_command.succeeds());
+    }
+
+    #[test]
+    fn hits_against_a_helpless_defender_are_critical() {
+        let mut attacker = Character::new(Class::Commoner);
+        attacker.strength = 14;
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.conditions.add(Condition::Helpless);
+
+        let attack_command = attacker.attack(&attackee, 12).with_confirmation_roll(1);
+        assert!(attack_command.is_critical());
+        assert_eq!(Some(5), attack_command.damage());
+        assert!(!attacker.attack(&attackee, 1).is_critical());
+    }
+
     #[test]
     fn the_level_modifier_is_applied_to_attack_commands() {
         let attack_command = AttackCommand {
             level_modifier: 1,
-            dice_roll: 1,
+            dice_roll: 2,
             attack_modifier: 0,
             defense_dexterity_modifier: 0,
             constitution_modifier: 0,
-            armor_class: 2,
+            armor_class: 3,
             defense_wisdom_modifier: 0,
             critical_hit_multiplier: 2,
             minimum_damage: 1,
             alignment_damage_modifier: 0,
+            ..AttackCommand::default()
         };
 
-        assert_eq!(true, attack_command.succeeds());
+        assert!(attack_command.succeeds());
     }
 
     #[test]
@@ -254,12 +917,12 @@ mod tests {
         // TODO: directly setup the attack command
         let attacker = Character::new(Class::Rogue);
         let mut attackee = Character::new(Class::Commoner);
-        attackee.armor_class = 10;
+        attackee.base_armor_class = 10;
         attackee.dexterity = 12;
         let dice_roll: u32 = 10;
 
         let attack_command = attacker.attack(&attackee, dice_roll);
-        assert_eq!(true, attack_command.succeeds());
+        assert!(attack_command.succeeds());
     }
 
     #[test]
@@ -267,12 +930,12 @@ mod tests {
         // TODO: directly setup the attack command
         let attacker = Character::new(Class::Rogue);
         let mut attackee = Character::new(Class::Commoner);
-        attackee.armor_class = 11;
+        attackee.base_armor_class = 11;
         attackee.dexterity = 8;
         let dice_roll: u32 = 10;
 
         let attack_command = attacker.attack(&attackee, dice_roll);
-        assert_eq!(true, attack_command.succeeds());
+        assert!(attack_command.succeeds());
     }
 
     #[test]
@@ -285,7 +948,7 @@ mod tests {
 
 
         let attack_command = attacker.attack(&attackee, dice_roll);
-        assert_eq!(true, attack_command.succeeds());
+        assert!(attack_command.succeeds());
     }
 
     #[test]
@@ -348,9 +1011,10 @@ mod tests {
             critical_hit_multiplier: 0,
             minimum_damage: 1,
             alignment_damage_modifier: 0,
+            ..AttackCommand::default()
         };
 
-        assert_eq!(attack_command.succeeds(), false);
+        assert!(!attack_command.succeeds());
     }
 
     impl Character {
@@ -431,6 +1095,58 @@ mod tests {
         assert_eq!(attack_command.damage(), Some(1));
     }
 
+    #[test]
+    fn a_paladins_alignment_damage_is_holy() {
+        let mut attacker = Character::new(Class::Paladin);
+        attacker.strength = 14;
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.alignment = Alignment::Evil;
+
+        let attack_command = attacker.attack(&attackee, 10);
+        assert_eq!(
+            vec![
+                DamagePacket::new(3, DamageType::Bludgeoning),
+                DamagePacket::new(2, DamageType::Holy),
+            ],
+            attack_command.damage_packets()
+        );
+    }
+
+    #[test]
+    fn a_weak_paladin_reports_the_damage_it_deals() {
+        let mut attacker = Character::new(Class::Paladin);
+        attacker.strength = 3;
+        let mut attackee = Character::new(Class::Fighter);
+        attackee.alignment = Alignment::Evil;
+
+        let attack_command = attacker.attack(&attackee, 20);
+        assert_eq!(
+            vec![
+                DamagePacket::new(0, DamageType::Bludgeoning),
+                DamagePacket::new(6, DamageType::Holy),
+            ],
+            attack_command.damage_packets()
+        );
+        assert_eq!(Some(6), attack_command.damage());
+
+        let event = resolve_combat(&attack_command, &mut attacker, &mut attackee);
+        assert_eq!(6, event.damage_dealt);
+        assert_eq!(6, attackee.damage);
+    }
+
+    #[test]
+    fn resolving_combat_applies_the_defenders_damage_traits() {
+        let mut attacker = Character::new(Class::Paladin);
+        attacker.strength = 14;
+        let mut undead = Character::new(Class::Commoner);
+        undead.alignment = Alignment::Evil;
+        undead.damage_traits.vulnerabilities.push(DamageType::Holy);
+        undead.damage_traits.damage_reduction = 2;
+
+        resolve_combat(&attacker.attack(&undead, 10), &mut attacker, &mut undead);
+        assert_eq!(5, undead.damage);
+    }
+
     #[test]
     fn as_a_paladin_my_attack_roll_is_increased_by_one_for_every_level() {
         let mut attacker = Character::new(Class::Paladin);
@@ -0,0 +1,191 @@
+use combat::DiceRollModifier;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Condition {
+    Stunned,
+    Prone,
+    Blinded,
+    Poisoned,
+    Helpless,
+    Grappled,
+    // Fighting bare-handed and off balance after losing a weapon.
+    Disarmed,
+    Blessed,
+    Shielded,
+}
+
+impl Condition {
+    pub fn attack_modifier(&self) -> DiceRollModifier {
+        match *self {
+            Condition::Prone | Condition::Disarmed => -4,
+            Condition::Blinded | Condition::Poisoned | Condition::Grappled => -2,
+            Condition::Blessed => 1,
+            Condition::Stunned | Condition::Helpless | Condition::Shielded => 0,
+        }
+    }
+
+    pub fn armor_class_modifier(&self) -> DiceRollModifier {
+        match *self {
+            Condition::Prone => -4,
+            Condition::Stunned | Condition::Blinded => -2,
+            Condition::Shielded => 4,
+            Condition::Poisoned |
+            Condition::Helpless |
+            Condition::Grappled |
+            Condition::Disarmed |
+            Condition::Blessed => 0,
+        }
+    }
+
+    pub fn loses_dexterity_bonus(&self) -> bool {
+        match *self {
+            Condition::Stunned | Condition::Blinded | Condition::Helpless => true,
+            Condition::Prone |
+            Condition::Poisoned |
+            Condition::Grappled |
+            Condition::Disarmed |
+            Condition::Blessed |
+            Condition::Shielded => false,
+        }
+    }
+
+    pub fn grants_automatic_critical(&self) -> bool {
+        *self == Condition::Helpless
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ActiveCondition {
+    pub condition: Condition,
+    pub remaining_rounds: Option<u32>,
+}
+
+// Conditions without a duration last until they are removed. Re-applying a
+// condition replaces its remaining duration.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Conditions {
+    active: Vec<ActiveCondition>,
+}
+
+impl Conditions {
+    pub fn add(&mut self, condition: Condition) {
+        self.apply(condition, None);
+    }
+
+    pub fn add_for(&mut self, condition: Condition, rounds: u32) {
+        self.apply(condition, Some(rounds));
+    }
+
+    fn apply(&mut self, condition: Condition, remaining_rounds: Option<u32>) {
+        self.remove(condition);
+        self.active.push(ActiveCondition {
+            condition,
+            remaining_rounds,
+        });
+    }
+
+    pub fn remove(&mut self, condition: Condition) {
+        self.active.retain(|active| active.condition != condition);
+    }
+
+    pub fn has(&self, condition: Condition) -> bool {
+        self.active.iter().any(|active| active.condition == condition)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ActiveCondition> {
+        self.active.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    // Counts one round off every timed condition and returns the ones that
+    // wore off.
+    pub fn tick(&mut self) -> Vec<Condition> {
+        let mut expired = Vec::new();
+
+        for active in &mut self.active {
+            if let Some(ref mut rounds) = active.remaining_rounds {
+                *rounds = rounds.saturating_sub(1);
+                if *rounds == 0 {
+                    expired.push(active.condition);
+                }
+            }
+        }
+
+        self.active.retain(|active| active.remaining_rounds != Some(0));
+        expired
+    }
+
+    pub fn attack_modifier(&self) -> DiceRollModifier {
+        self.active.iter().map(|active| active.condition.attack_modifier()).sum()
+    }
+
+    pub fn armor_class_modifier(&self) -> DiceRollModifier {
+        self.active
+            .iter()
+            .map(|active| active.condition.armor_class_modifier())
+            .sum()
+    }
+
+    pub fn loses_dexterity_bonus(&self) -> bool {
+        self.active
+            .iter()
+            .any(|active| active.condition.loses_dexterity_bonus())
+    }
+
+    pub fn grants_automatic_critical(&self) -> bool {
+        self.active
+            .iter()
+            .any(|active| active.condition.grants_automatic_critical())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timed_conditions_wear_off() {
+        let mut conditions = Conditions::default();
+        conditions.add_for(Condition::Stunned, 2);
+        conditions.add(Condition::Poisoned);
+
+        assert!(conditions.tick().is_empty());
+        assert_eq!(vec![Condition::Stunned], conditions.tick());
+        assert!(!conditions.has(Condition::Stunned));
+        assert!(conditions.has(Condition::Poisoned));
+    }
+
+    #[test]
+    fn reapplying_a_condition_refreshes_its_duration() {
+        let mut conditions = Conditions::default();
+        conditions.add_for(Condition::Prone, 1);
+        conditions.add_for(Condition::Prone, 3);
+
+        assert_eq!(1, conditions.iter().count());
+        assert!(conditions.tick().is_empty());
+    }
+
+    #[test]
+    fn modifiers_stack_across_conditions() {
+        let mut conditions = Conditions::default();
+        conditions.add(Condition::Prone);
+        conditions.add(Condition::Poisoned);
+
+        assert_eq!(-6, conditions.attack_modifier());
+        assert_eq!(-4, conditions.armor_class_modifier());
+        assert!(!conditions.loses_dexterity_bonus());
+    }
+
+    #[test]
+    fn some_conditions_help() {
+        let mut conditions = Conditions::default();
+        conditions.add(Condition::Blessed);
+        conditions.add(Condition::Shielded);
+
+        assert_eq!(1, conditions.attack_modifier());
+        assert_eq!(4, conditions.armor_class_modifier());
+    }
+}
@@ -0,0 +1,360 @@
+use character::Character;
+use combat::{resolve_combat, AttackCommand, CombatEvent};
+use conditions::Condition;
+use damage::DamageType;
+use dice::{Dice, DiceRoller};
+use inventory::{item_id, InventoryError, Item};
+use ranged::{RangedAttack, RangedAttackError};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ConsumableEffect {
+    Healing(Dice),
+    // Thrown at a target with a ranged touch attack.
+    Splash {
+        damage_dice: Dice,
+        damage_type: DamageType,
+        range_increment: u32,
+    },
+    Buff { condition: Condition, rounds: u32 },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Consumable {
+    pub id: String,
+    pub name: String,
+    pub effect: ConsumableEffect,
+    pub weight: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ConsumableResult {
+    Healed { amount: u32 },
+    Thrown(CombatEvent),
+    Buffed { condition: Condition, rounds: u32 },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ConsumableError {
+    Inventory(InventoryError),
+    NotConsumable { id: String },
+    NeedsTarget { id: String },
+    NotThrowable { id: String },
+    Ranged(RangedAttackError),
+}
+
+impl Consumable {
+    pub fn new(name: &str, effect: ConsumableEffect) -> Self {
+        Self {
+            id: item_id(name),
+            name: name.to_string(),
+            effect,
+            weight: 0,
+        }
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn potion_of_healing() -> Self {
+        Consumable::new("potion of healing", ConsumableEffect::Healing(Dice::new(2, 8)))
+    }
+
+    pub fn alchemists_fire() -> Self {
+        Consumable::new(
+            "alchemist's fire",
+            ConsumableEffect::Splash {
+                damage_dice: Dice::new(1, 6),
+                damage_type: DamageType::Fire,
+                range_increment: 10,
+            },
+        )
+        .with_weight(1)
+    }
+
+    pub fn flask_of_acid() -> Self {
+        Consumable::new(
+            "flask of acid",
+            ConsumableEffect::Splash {
+                damage_dice: Dice::new(1, 6),
+                damage_type: DamageType::Acid,
+                range_increment: 10,
+            },
+        )
+        .with_weight(1)
+    }
+
+    pub fn scroll_of_bless() -> Self {
+        Consumable::new(
+            "scroll of bless",
+            ConsumableEffect::Buff {
+                condition: Condition::Blessed,
+                rounds: 10,
+            },
+        )
+    }
+
+    pub fn scroll_of_shield() -> Self {
+        Consumable::new(
+            "scroll of shield",
+            ConsumableEffect::Buff {
+                condition: Condition::Shielded,
+                rounds: 10,
+            },
+        )
+    }
+}
+
+impl Character {
+    fn consumable_effect(&self, id: &str) -> Result<ConsumableEffect, ConsumableError> {
+        match self.inventory.get(id) {
+            Some(Item::Consumable(consumable)) => Ok(consumable.effect),
+            Some(_) => Err(ConsumableError::NotConsumable { id: id.to_string() }),
+            None => Err(ConsumableError::Inventory(InventoryError::NotCarried {
+                id: id.to_string(),
+            })),
+        }
+    }
+
+    fn consume(&mut self, id: &str) -> Result<(), ConsumableError> {
+        self.inventory
+            .remove(id, 1)
+            .map(|_| ())
+            .map_err(ConsumableError::Inventory)
+    }
+
+    // Drinks a potion or reads a scroll; splash items have to be thrown.
+    pub fn use_item<R>(
+        &mut self,
+        id: &str,
+        roller: &mut R,
+    ) -> Result<ConsumableResult, ConsumableError>
+    where
+        R: DiceRoller + ?Sized,
+    {
+        let result = match self.consumable_effect(id)? {
+            ConsumableEffect::Healing(dice) => ConsumableResult::Healed {
+                amount: self.heal(dice.roll(roller)),
+            },
+            ConsumableEffect::Buff { condition, rounds } => {
+                self.conditions.add_for(condition, rounds);
+                ConsumableResult::Buffed { condition, rounds }
+            }
+            ConsumableEffect::Splash { .. } => {
+                return Err(ConsumableError::NeedsTarget { id: id.to_string() })
+            }
+        };
+
+        self.consume(id)?;
+        Ok(result)
+    }
+
+    // A splash weapon only has to touch the target, so armor, shields and
+    // magic protection do not count. It never adds ability damage and cannot
+    // critically hit. The flask is spent whether it hits or not.
+    pub fn throw_item<R>(
+        &mut self,
+        id: &str,
+        target: &mut Character,
+        distance: u32,
+        roller: &mut R,
+    ) -> Result<ConsumableResult, ConsumableError>
+    where
+        R: DiceRoller + ?Sized,
+    {
+        self.throw_at(id, target, distance, roller).map(ConsumableResult::Thrown)
+    }
+
+    pub(crate) fn throw_at<R>(
+        &mut self,
+        id: &str,
+        target: &mut Character,
+        distance: u32,
+        roller: &mut R,
+    ) -> Result<CombatEvent, ConsumableError>
+    where
+        R: DiceRoller + ?Sized,
+    {
+        let (damage_dice, damage_type, range_increment) = match self.consumable_effect(id)? {
+            ConsumableEffect::Splash {
+                damage_dice,
+                damage_type,
+                range_increment,
+            } => (damage_dice, damage_type, range_increment),
+            _ => {
+                return Err(ConsumableError::NotThrowable { id: id.to_string() })
+            }
+        };
+
+        let ranged_attack = RangedAttack::new(distance, range_increment)
+            .and_then(|ranged_attack| self.ranged_attack(target, roller.d20(), ranged_attack))
+            .map_err(ConsumableError::Ranged)?;
+        let command = AttackCommand {
+            armor_class: target.base_armor_class,
+            damage_modifier: 0,
+            alignment_damage_modifier: 0,
+            weapon_bonus: 0,
+            proficiency_penalty: 0,
+            two_weapon_penalty: 0,
+            can_critical_hit: false,
+            damage_dice: Some(damage_dice),
+            damage_type,
+            ..ranged_attack
+        }
+        .roll_damage(roller);
+
+        self.consume(id)?;
+        Ok(resolve_combat(&command, self, target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use armor::Armor;
+    use character::Class;
+    use combat::AttackOutcome;
+    use dice::LoadedDice;
+
+    #[test]
+    fn a_potion_heals_and_is_used_up() {
+        let mut character = Character::new(Class::Fighter);
+        character.damage = 8;
+        character.inventory.add(Item::Consumable(Consumable::potion_of_healing()), 2);
+
+        let mut dice = LoadedDice::new(vec![3, 2]).unwrap();
+
+        let result = character.use_item("potion-of-healing", &mut dice);
+
+        assert_eq!(Ok(ConsumableResult::Healed { amount: 5 }), result);
+        assert_eq!(3, character.damage);
+        assert_eq!(1, character.inventory.quantity("potion-of-healing"));
+    }
+
+    #[test]
+    fn a_scroll_applies_a_timed_condition() {
+        let mut character = Character::new(Class::Fighter);
+        character.inventory.add(Item::Consumable(Consumable::scroll_of_bless()), 1);
+        let attackee = Character::new(Class::Commoner);
+
+        character
+            .use_item("scroll-of-bless", &mut LoadedDice::new(vec![1]).unwrap())
+            .unwrap();
+
+        assert!(character.conditions.has(Condition::Blessed));
+        assert_eq!(1, character.attack(&attackee, 10).condition_attack_modifier);
+        assert!(character.inventory.is_empty());
+    }
+
+    #[test]
+    fn only_consumables_can_be_used() {
+        let mut character = Character::new(Class::Fighter);
+        character.inventory.add(Item::Armor(Armor::leather()), 1);
+        character.inventory.add(Item::Consumable(Consumable::flask_of_acid()), 1);
+        let mut dice = LoadedDice::new(vec![10]).unwrap();
+
+        assert_eq!(
+            Err(ConsumableError::NotConsumable {
+                id: "leather-armor".to_string(),
+            }),
+            character.use_item("leather-armor", &mut dice)
+        );
+        assert_eq!(
+            Err(ConsumableError::NeedsTarget {
+                id: "flask-of-acid".to_string(),
+            }),
+            character.use_item("flask-of-acid", &mut dice)
+        );
+        assert_eq!(1, character.inventory.quantity("flask-of-acid"));
+    }
+
+    #[test]
+    fn a_thrown_oil_ignores_armor_and_deals_its_damage_type() {
+        let mut thrower = Character::new(Class::Commoner);
+        thrower.inventory.add(Item::Consumable(Consumable::alchemists_fire()), 1);
+        let mut target = Character::new(Class::Fighter);
+        target.wear(Armor::plate()).unwrap();
+        target.damage_traits.vulnerabilities.push(DamageType::Fire);
+
+        let mut dice = LoadedDice::new(vec![12, 4]).unwrap();
+
+        let result = thrower.throw_item("alchemists-fire", &mut target, 10, &mut dice);
+
+        match result {
+            Ok(ConsumableResult::Thrown(event)) => {
+                assert_eq!(10, event.target_number);
+                assert_eq!(8, event.damage_dealt);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert_eq!(8, target.damage);
+        assert!(thrower.inventory.is_empty());
+    }
+
+    #[test]
+    fn a_thrown_flask_never_scores_a_critical_hit() {
+        let mut thrower = Character::new(Class::Commoner);
+        thrower.inventory.add(Item::Consumable(Consumable::flask_of_acid()), 2);
+        let mut target = Character::new(Class::Fighter);
+
+        let event = thrower
+            .throw_at("flask-of-acid", &mut target, 5, &mut LoadedDice::new(vec![20, 3]).unwrap())
+            .unwrap();
+        assert_eq!(AttackOutcome::Hit { damage: 3, threat: None }, event.outcome);
+
+        target.conditions.add(Condition::Helpless);
+        let event = thrower
+            .throw_at("flask-of-acid", &mut target, 5, &mut LoadedDice::new(vec![15, 3]).unwrap())
+            .unwrap();
+        assert_eq!(AttackOutcome::Hit { damage: 3, threat: None }, event.outcome);
+    }
+
+    #[test]
+    fn an_oil_thrown_out_of_range_is_not_spent() {
+        let mut thrower = Character::new(Class::Commoner);
+        thrower.inventory.add(Item::Consumable(Consumable::flask_of_acid()), 1);
+        let mut target = Character::new(Class::Commoner);
+
+        let mut dice = LoadedDice::new(vec![20]).unwrap();
+
+        let result = thrower.throw_item("flask-of-acid", &mut target, 150, &mut dice);
+
+        assert_eq!(
+            Err(ConsumableError::Ranged(RangedAttackError::OutOfRange {
+                distance: 150,
+                maximum_range: 100,
+            })),
+            result
+        );
+        assert_eq!(1, thrower.inventory.quantity("flask-of-acid"));
+    }
+
+    #[test]
+    fn a_splash_item_without_a_range_increment_cannot_be_thrown() {
+        let mut thrower = Character::new(Class::Commoner);
+        let flask = Consumable::new(
+            "clay flask",
+            ConsumableEffect::Splash {
+                damage_dice: Dice::new(1, 4),
+                damage_type: DamageType::Fire,
+                range_increment: 0,
+            },
+        );
+        thrower.inventory.add(Item::Consumable(flask), 1);
+        let mut target = Character::new(Class::Commoner);
+
+        let mut dice = LoadedDice::new(vec![20]).unwrap();
+
+        assert_eq!(
+            Err(ConsumableError::Ranged(RangedAttackError::ZeroRangeIncrement)),
+            thrower.throw_item("clay-flask", &mut target, 5, &mut dice)
+        );
+        assert_eq!(1, thrower.inventory.quantity("clay-flask"));
+    }
+}
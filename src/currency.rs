@@ -0,0 +1,256 @@
+use std::cmp;
+
+use character::Character;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Denomination {
+    Copper,
+    Silver,
+    Gold,
+    Platinum,
+}
+
+// From the most valuable coin down.
+const DENOMINATIONS: [Denomination; 4] = [
+    Denomination::Platinum,
+    Denomination::Gold,
+    Denomination::Silver,
+    Denomination::Copper,
+];
+
+impl Denomination {
+    pub fn copper_value(&self) -> u64 {
+        match *self {
+            Denomination::Copper => 1,
+            Denomination::Silver => 10,
+            Denomination::Gold => 100,
+            Denomination::Platinum => 1000,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CurrencyError {
+    InsufficientFunds { cost: u64, available: u64 },
+    NotEnoughCoins { denomination: Denomination, requested: u64, available: u64 },
+    UnevenExchange { value: u64, denomination: Denomination },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct CoinPurse {
+    pub copper: u64,
+    pub silver: u64,
+    pub gold: u64,
+    pub platinum: u64,
+}
+
+impl CoinPurse {
+    // Makes up the value with as few coins as possible.
+    pub fn from_copper(value: u64) -> Self {
+        Self {
+            platinum: value / 1000,
+            gold: value % 1000 / 100,
+            silver: value % 100 / 10,
+            copper: value % 10,
+        }
+    }
+
+    pub fn coins(&self, denomination: Denomination) -> u64 {
+        match denomination {
+            Denomination::Copper => self.copper,
+            Denomination::Silver => self.silver,
+            Denomination::Gold => self.gold,
+            Denomination::Platinum => self.platinum,
+        }
+    }
+
+    fn coins_mut(&mut self, denomination: Denomination) -> &mut u64 {
+        match denomination {
+            Denomination::Copper => &mut self.copper,
+            Denomination::Silver => &mut self.silver,
+            Denomination::Gold => &mut self.gold,
+            Denomination::Platinum => &mut self.platinum,
+        }
+    }
+
+    pub fn total_copper(&self) -> u64 {
+        self.copper +
+            self.silver * Denomination::Silver.copper_value() +
+            self.gold * Denomination::Gold.copper_value() +
+            self.platinum * Denomination::Platinum.copper_value()
+    }
+
+    pub fn add(&mut self, denomination: Denomination, count: u64) {
+        *self.coins_mut(denomination) += count;
+    }
+
+    pub fn add_copper(&mut self, value: u64) {
+        let coins = CoinPurse::from_copper(value);
+        self.copper += coins.copper;
+        self.silver += coins.silver;
+        self.gold += coins.gold;
+        self.platinum += coins.platinum;
+    }
+
+    // Pays with the largest coins that do not overshoot, then breaks the
+    // smallest coin that covers what is left. Only that coin's change comes
+    // back, in as few coins as possible.
+    pub fn spend(&mut self, cost: u64) -> Result<(), CurrencyError> {
+        let available = self.total_copper();
+        if cost > available {
+            return Err(CurrencyError::InsufficientFunds { cost, available });
+        }
+
+        let mut remaining = cost;
+        for denomination in &DENOMINATIONS {
+            let coins = self.coins_mut(*denomination);
+            let paid = cmp::min(*coins, remaining / denomination.copper_value());
+            *coins -= paid;
+            remaining -= paid * denomination.copper_value();
+        }
+
+        let broken = DENOMINATIONS.iter().rev().cloned().find(|denomination| {
+            remaining > 0 && self.coins(*denomination) > 0 &&
+                denomination.copper_value() > remaining
+        });
+        if let Some(broken) = broken {
+            *self.coins_mut(broken) -= 1;
+            self.add_copper(broken.copper_value() - remaining);
+        }
+        Ok(())
+    }
+
+    // Exchanges coins of one denomination for the same value in another.
+    pub fn exchange(
+        &mut self,
+        from: Denomination,
+        to: Denomination,
+        count: u64,
+    ) -> Result<(), CurrencyError> {
+        let available = self.coins(from);
+        if count > available {
+            return Err(CurrencyError::NotEnoughCoins {
+                denomination: from,
+                requested: count,
+                available,
+            });
+        }
+
+        let value = count * from.copper_value();
+        if !value.is_multiple_of(to.copper_value()) {
+            return Err(CurrencyError::UnevenExchange {
+                value,
+                denomination: to,
+            });
+        }
+
+        *self.coins_mut(from) -= count;
+        *self.coins_mut(to) += value / to.copper_value();
+        Ok(())
+    }
+}
+
+impl Character {
+    pub fn wealth(&self) -> u64 {
+        self.purse.total_copper()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_purse_counts_every_coin_in_copper() {
+        let purse = CoinPurse {
+            copper: 3,
+            silver: 2,
+            gold: 1,
+            platinum: 1,
+        };
+
+        assert_eq!(1123, purse.total_copper());
+        assert_eq!(purse, CoinPurse::from_copper(1123));
+    }
+
+    #[test]
+    fn spending_gives_change() {
+        let mut purse = CoinPurse::default();
+        purse.add(Denomination::Gold, 2);
+
+        purse.spend(35).unwrap();
+
+        assert_eq!(CoinPurse::from_copper(165), purse);
+        assert_eq!(
+            Err(CurrencyError::InsufficientFunds {
+                cost: 200,
+                available: 165,
+            }),
+            purse.spend(200)
+        );
+    }
+
+    #[test]
+    fn spending_only_breaks_the_coins_it_needs() {
+        let mut purse = CoinPurse::default();
+        purse.add(Denomination::Gold, 15);
+        purse.add(Denomination::Silver, 20);
+
+        purse.spend(1).unwrap();
+        assert_eq!(
+            CoinPurse {
+                copper: 9,
+                silver: 19,
+                gold: 15,
+                platinum: 0,
+            },
+            purse
+        );
+
+        purse.spend(1_500).unwrap();
+        assert_eq!(
+            CoinPurse {
+                copper: 9,
+                silver: 19,
+                gold: 0,
+                platinum: 0,
+            },
+            purse
+        );
+    }
+
+    #[test]
+    fn coins_can_be_exchanged_between_denominations() {
+        let mut purse = CoinPurse::default();
+        purse.add(Denomination::Gold, 3);
+
+        purse.exchange(Denomination::Gold, Denomination::Silver, 2).unwrap();
+        assert_eq!(1, purse.gold);
+        assert_eq!(20, purse.silver);
+
+        assert_eq!(
+            Err(CurrencyError::UnevenExchange {
+                value: 200,
+                denomination: Denomination::Platinum,
+            }),
+            purse.exchange(Denomination::Silver, Denomination::Platinum, 20)
+        );
+        purse.exchange(Denomination::Silver, Denomination::Gold, 10).unwrap();
+        assert_eq!(2, purse.gold);
+        assert_eq!(10, purse.silver);
+    }
+
+    #[test]
+    fn exchanging_needs_the_coins() {
+        let mut purse = CoinPurse::default();
+
+        assert_eq!(
+            Err(CurrencyError::NotEnoughCoins {
+                denomination: Denomination::Gold,
+                requested: 1,
+                available: 0,
+            }),
+            purse.exchange(Denomination::Gold, Denomination::Copper, 1)
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::cmp;
+
+use character::Character;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DamageType {
+    Slashing,
+    Piercing,
+    #[default]
+    Bludgeoning,
+    Fire,
+    Cold,
+    Acid,
+    Lightning,
+    Holy,
+    Unholy,
+}
+
+impl DamageType {
+    pub fn is_physical(&self) -> bool {
+        matches!(
+            *self,
+            DamageType::Slashing | DamageType::Piercing | DamageType::Bludgeoning
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DamagePacket {
+    pub amount: i32,
+    pub damage_type: DamageType,
+}
+
+impl DamagePacket {
+    pub fn new(amount: i32, damage_type: DamageType) -> Self {
+        Self {
+            amount,
+            damage_type,
+        }
+    }
+}
+
+// Immunities win over everything else; resistance halves (rounding down) and
+// vulnerability doubles. Damage reduction is subtracted from the physical
+// damage of an attack as a whole.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct DamageTraits {
+    pub resistances: Vec<DamageType>,
+    pub immunities: Vec<DamageType>,
+    pub vulnerabilities: Vec<DamageType>,
+    pub damage_reduction: u32,
+}
+
+impl DamageTraits {
+    pub fn damage_taken(&self, packets: &[DamagePacket]) -> u32 {
+        let mut physical = 0;
+        let mut energy = 0;
+
+        for packet in packets {
+            let damage_type = packet.damage_type;
+            let mut amount = cmp::max(packet.amount, 0);
+
+            if self.immunities.contains(&damage_type) {
+                continue;
+            }
+            if self.resistances.contains(&damage_type) {
+                amount /= 2;
+            }
+            if self.vulnerabilities.contains(&damage_type) {
+                amount *= 2;
+            }
+
+            if damage_type.is_physical() {
+                physical += amount;
+            } else {
+                energy += amount;
+            }
+        }
+
+        let physical = cmp::max(physical - self.damage_reduction as i32, 0);
+        (physical + energy) as u32
+    }
+}
+
+impl Character {
+    pub fn take_damage(&mut self, packets: &[DamagePacket]) -> u32 {
+        let damage_taken = self.damage_traits.damage_taken(packets);
+        self.damage += damage_taken;
+        damage_taken
+    }
+
+    // Healing never takes a character above their maximum hit points, and
+    // returns how much was actually restored.
+    pub fn heal(&mut self, amount: u32) -> u32 {
+        let healed = cmp::min(amount, self.damage);
+        self.damage -= healed;
+        healed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Class;
+
+    #[test]
+    fn untyped_characters_take_damage_as_dealt() {
+        let mut character = Character::new(Class::Commoner);
+
+        let damage_taken = character.take_damage(&[
+            DamagePacket::new(3, DamageType::Slashing),
+            DamagePacket::new(2, DamageType::Fire),
+        ]);
+
+        assert_eq!(5, damage_taken);
+        assert_eq!(5, character.damage);
+    }
+
+    #[test]
+    fn resistances_immunities_and_vulnerabilities_apply_per_type() {
+        let traits = DamageTraits {
+            resistances: vec![DamageType::Cold],
+            immunities: vec![DamageType::Fire],
+            vulnerabilities: vec![DamageType::Holy],
+            damage_reduction: 0,
+        };
+
+        assert_eq!(2, traits.damage_taken(&[DamagePacket::new(5, DamageType::Cold)]));
+        assert_eq!(0, traits.damage_taken(&[DamagePacket::new(5, DamageType::Fire)]));
+        assert_eq!(10, traits.damage_taken(&[DamagePacket::new(5, DamageType::Holy)]));
+    }
+
+    #[test]
+    fn damage_reduction_only_soaks_physical_damage() {
+        let traits = DamageTraits {
+            damage_reduction: 5,
+            ..DamageTraits::default()
+        };
+
+        let packets = [
+            DamagePacket::new(3, DamageType::Piercing),
+            DamagePacket::new(4, DamageType::Slashing),
+            DamagePacket::new(2, DamageType::Acid),
+        ];
+        assert_eq!(4, traits.damage_taken(&packets));
+    }
+
+    #[test]
+    fn healing_stops_at_full_hit_points() {
+        let mut character = Character::new(Class::Commoner);
+        character.damage = 6;
+
+        assert_eq!(4, character.heal(4));
+        assert_eq!(2, character.heal(4));
+        assert_eq!(0, character.damage);
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+
+pub trait DiceRoller {
+    fn roll(&mut self, sides: u32) -> u32;
+
+    fn d20(&mut self) -> u32 {
+        self.roll(20)
+    }
+}
+
+impl<R: Rng> DiceRoller for R {
+    fn roll(&mut self, sides: u32) -> u32 {
+        self.gen_range(1, sides + 1)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Dice {
+    pub count: u32,
+    pub sides: u32,
+}
+
+impl Dice {
+    pub fn new(count: u32, sides: u32) -> Self {
+        Self { count, sides }
+    }
+
+    pub fn roll<R>(&self, roller: &mut R) -> u32
+    where
+        R: DiceRoller + ?Sized,
+    {
+        (0..self.count).map(|_| roller.roll(self.sides)).sum()
+    }
+
+    // Rounded down, so a 1d8 averages 4.
+    pub fn average(&self) -> u32 {
+        self.count * (self.sides + 1) / 2
+    }
+
+    pub fn minimum(&self) -> u32 {
+        self.count
+    }
+
+    pub fn maximum(&self) -> u32 {
+        self.count * self.sides
+    }
+}
+
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}d{}", self.count, self.sides)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseDiceError {
+    pub notation: String,
+}
+
+// Large enough for any table, small enough that sums never overflow.
+const MAX_COUNT: u32 = 100;
+const MAX_SIDES: u32 = 100;
+
+// Parses "XdY" notation; both numbers must be between 1 and 100.
+impl FromStr for Dice {
+    type Err = ParseDiceError;
+
+    fn from_str(notation: &str) -> Result<Self, Self::Err> {
+        let error = || ParseDiceError {
+            notation: notation.to_string(),
+        };
+        let mut parts = notation.trim().splitn(2, 'd');
+        let count = parts.next().and_then(|count| count.parse().ok()).ok_or_else(error)?;
+        let sides = parts.next().and_then(|sides| sides.parse().ok()).ok_or_else(error)?;
+
+        if !(1..=MAX_COUNT).contains(&count) || !(1..=MAX_SIDES).contains(&sides) {
+            return Err(error());
+        }
+        Ok(Dice::new(count, sides))
+    }
+}
+
+// Replays a fixed sequence of rolls, wrapping around when exhausted.
+#[derive(Debug, Clone)]
+pub struct LoadedDice {
+    rolls: Vec<u32>,
+    next: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct NoRollsError;
+
+impl LoadedDice {
+    pub fn new(rolls: Vec<u32>) -> Result<Self, NoRollsError> {
+        if rolls.is_empty() {
+            return Err(NoRollsError);
+        }
+        Ok(Self { rolls, next: 0 })
+    }
+}
+
+impl DiceRoller for LoadedDice {
+    fn roll(&mut self, sides: u32) -> u32 {
+        let roll = self.rolls[self.next % self.rolls.len()];
+        self.next += 1;
+        roll.clamp(1, sides)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, StdRng};
+
+    #[test]
+    fn rolls_stay_within_the_die() {
+        let mut rng = StdRng::from_seed(&[42][..]);
+
+        for _ in 0..100 {
+            let roll = rng.roll(6);
+            assert!((1..=6).contains(&roll));
+        }
+    }
+
+    #[test]
+    fn dice_sum_every_die_rolled() {
+        let two_d6 = Dice::new(2, 6);
+
+        assert_eq!(9, two_d6.roll(&mut LoadedDice::new(vec![4, 5]).unwrap()));
+        assert_eq!(7, two_d6.average());
+        assert_eq!(2, two_d6.minimum());
+        assert_eq!(12, two_d6.maximum());
+        assert_eq!("2d6", two_d6.to_string());
+    }
+
+    #[test]
+    fn dice_parse_from_their_notation() {
+        assert_eq!(Ok(Dice::new(1, 8)), "1d8".parse());
+        assert!("d8".parse::<Dice>().is_err());
+        assert!("1d0".parse::<Dice>().is_err());
+        assert!("4294967295d6".parse::<Dice>().is_err());
+        assert!("1d101".parse::<Dice>().is_err());
+        assert!("1x8".parse::<Dice>().is_err());
+    }
+
+    #[test]
+    fn loaded_dice_need_rolls_to_replay() {
+        assert_eq!(Err(NoRollsError), LoadedDice::new(vec![]).map(|_| ()));
+    }
+
+    #[test]
+    fn loaded_dice_replay_their_rolls() {
+        let mut dice = LoadedDice::new(vec![3, 20]).unwrap();
+
+        assert_eq!(3, dice.roll(8));
+        assert_eq!(8, dice.roll(8));
+        assert_eq!(3, dice.d20());
+    }
+}
@@ -0,0 +1,660 @@
+use std::cmp::Ordering;
+
+use character::Character;
+use combat::{resolve_combat, AttackCommand, CombatEvent};
+use consumables::ConsumableError;
+use dice::DiceRoller;
+use event_log::EventLog;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Side {
+    Heroes,
+    Monsters,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct CombatantId(pub(crate) usize);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Initiative {
+    pub dice_roll: u32,
+    pub dexterity_modifier: i32,
+    pub tiebreaker: u32,
+}
+
+impl Initiative {
+    pub fn total(&self) -> i32 {
+        self.dice_roll as i32 + self.dexterity_modifier
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EncounterError {
+    NotStarted,
+    Over,
+    UnknownCombatant(CombatantId),
+    NotTheirTurn { attacker: CombatantId, current: CombatantId },
+    TargetsItself(CombatantId),
+    Dead(CombatantId),
+    OffHandUsed(CombatantId),
+    Consumable(ConsumableError),
+}
+
+pub struct Combatant {
+    pub character: Character,
+    pub side: Side,
+    pub initiative: Initiative,
+}
+
+pub struct Encounter {
+    combatants: Vec<Combatant>,
+    turn_order: Vec<CombatantId>,
+    round: u32,
+    turn: usize,
+    // Combatants who have already made their off-hand attack this round.
+    off_hand_attacked: Vec<CombatantId>,
+    log: EventLog,
+}
+
+impl Default for Encounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Encounter {
+    pub fn new() -> Self {
+        Self {
+            combatants: Vec::new(),
+            turn_order: Vec::new(),
+            round: 0,
+            turn: 0,
+            off_hand_attacked: Vec::new(),
+            log: EventLog::new(),
+        }
+    }
+
+    // Anyone joining after initiative has been rolled acts last in the round.
+    pub fn add(&mut self, character: Character, side: Side) -> CombatantId {
+        self.combatants.push(Combatant {
+            character,
+            side,
+            initiative: Initiative::default(),
+        });
+        let id = CombatantId(self.combatants.len() - 1);
+        if self.round > 0 {
+            self.turn_order.push(id);
+        }
+        id
+    }
+
+    // Returns `None` for an id from another encounter.
+    pub fn combatant(&self, id: CombatantId) -> Option<&Combatant> {
+        self.combatants.get(id.0)
+    }
+
+    pub fn combatant_mut(&mut self, id: CombatantId) -> Option<&mut Combatant> {
+        self.combatants.get_mut(id.0)
+    }
+
+    pub fn turn_order(&self) -> &[CombatantId] {
+        &self.turn_order
+    }
+
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn log(&self) -> &EventLog {
+        &self.log
+    }
+
+    // Rolls d20 + dexterity modifier for everyone and starts round one. Ties go
+    // to the higher dexterity modifier, then the higher tiebreaker d20.
+    pub fn roll_initiative<R>(&mut self, roller: &mut R)
+    where
+        R: DiceRoller + ?Sized,
+    {
+        for combatant in &mut self.combatants {
+            combatant.initiative = Initiative {
+                dice_roll: roller.d20(),
+                dexterity_modifier: combatant.character.dexterity_modifier(),
+                tiebreaker: roller.d20(),
+            };
+        }
+
+        let combatants = &self.combatants;
+        let mut turn_order: Vec<CombatantId> = (0..combatants.len()).map(CombatantId).collect();
+        turn_order.sort_by(|a, b| {
+            let a = combatants[a.0].initiative;
+            let b = combatants[b.0].initiative;
+
+            b.total()
+                .cmp(&a.total())
+                .then(b.dexterity_modifier.cmp(&a.dexterity_modifier))
+                .then(b.tiebreaker.cmp(&a.tiebreaker))
+        });
+
+        self.turn_order = turn_order;
+        self.round = 1;
+        self.turn = 0;
+        self.off_hand_attacked.clear();
+
+        if !self.is_over() && !self.is_alive(self.turn_order[0]) {
+            self.next_turn();
+        }
+    }
+
+    pub fn current(&self) -> Option<CombatantId> {
+        if self.round == 0 || self.is_over() {
+            None
+        } else {
+            Some(self.turn_order[self.turn])
+        }
+    }
+
+    // Advances to the next living combatant, starting a new round when the
+    // order wraps around. Returns `None` once the encounter is over, or when a
+    // full pass finds nobody alive to act.
+    pub fn next_turn(&mut self) -> Option<CombatantId> {
+        if self.round == 0 || self.is_over() {
+            return None;
+        }
+
+        for _ in 0..self.turn_order.len() {
+            self.turn += 1;
+            if self.turn == self.turn_order.len() {
+                self.turn = 0;
+                self.start_round();
+            }
+
+            if self.is_alive(self.turn_order[self.turn]) {
+                return Some(self.turn_order[self.turn]);
+            }
+        }
+        None
+    }
+
+    fn start_round(&mut self) {
+        self.round += 1;
+        self.off_hand_attacked.clear();
+
+        for combatant in &mut self.combatants {
+            combatant.character.conditions.tick();
+        }
+    }
+
+    // Rolls the d20 and then the weapon's damage dice.
+    pub fn attack<R>(
+        &mut self,
+        attacker: CombatantId,
+        attackee: CombatantId,
+        roller: &mut R,
+    ) -> Result<CombatEvent, EncounterError>
+    where
+        R: DiceRoller + ?Sized,
+    {
+        self.resolve(attacker, attackee, |attacker, attackee| {
+            attacker.attack(attackee, roller.d20()).roll_damage(roller)
+        })
+    }
+
+    // Returns `None` when the attacker is not fighting with two weapons. The
+    // off hand attacks at most once per round.
+    pub fn off_hand_attack<R>(
+        &mut self,
+        attacker: CombatantId,
+        attackee: CombatantId,
+        roller: &mut R,
+    ) -> Result<Option<CombatEvent>, EncounterError>
+    where
+        R: DiceRoller + ?Sized,
+    {
+        if self.off_hand_attacked.contains(&attacker) {
+            return Err(EncounterError::OffHandUsed(attacker));
+        }
+
+        let event = self.resolve_optional(attacker, attackee, |attacker, attackee| {
+            attacker
+                .off_hand_attack(attackee, roller.d20())
+                .map(|command| command.roll_damage(roller))
+        })?;
+
+        if event.is_some() {
+            self.off_hand_attacked.push(attacker);
+        }
+        Ok(event)
+    }
+
+    // Throws a splash weapon from the thrower's inventory; the same turn rules
+    // apply as for any other attack.
+    pub fn throw_item<R>(
+        &mut self,
+        thrower: CombatantId,
+        target: CombatantId,
+        id: &str,
+        distance: u32,
+        roller: &mut R,
+    ) -> Result<CombatEvent, EncounterError>
+    where
+        R: DiceRoller + ?Sized,
+    {
+        self.check_attack(thrower, target)?;
+
+        let event = {
+            let (thrower, target) = self.characters_mut(thrower, target);
+            thrower
+                .throw_at(id, target, distance, roller)
+                .map_err(EncounterError::Consumable)?
+        };
+
+        self.log.push(self.round, thrower, target, event.clone());
+        Ok(event)
+    }
+
+    // Only the living combatant whose turn it is may attack, and only another
+    // living combatant.
+    fn check_attack(
+        &self,
+        attacker: CombatantId,
+        attackee: CombatantId,
+    ) -> Result<(), EncounterError> {
+        for &id in &[attacker, attackee] {
+            if id.0 >= self.combatants.len() {
+                return Err(EncounterError::UnknownCombatant(id));
+            }
+        }
+        if self.round == 0 {
+            return Err(EncounterError::NotStarted);
+        }
+
+        let current = self.current().ok_or(EncounterError::Over)?;
+        if attacker != current {
+            return Err(EncounterError::NotTheirTurn { attacker, current });
+        }
+        if attacker == attackee {
+            return Err(EncounterError::TargetsItself(attacker));
+        }
+        match [attacker, attackee].iter().find(|&&id| !self.is_alive(id)) {
+            Some(&id) => Err(EncounterError::Dead(id)),
+            None => Ok(()),
+        }
+    }
+
+    fn resolve<F>(
+        &mut self,
+        attacker: CombatantId,
+        attackee: CombatantId,
+        attack: F,
+    ) -> Result<CombatEvent, EncounterError>
+    where
+        F: FnOnce(&Character, &Character) -> AttackCommand,
+    {
+        self.check_attack(attacker, attackee)?;
+
+        let command = attack(
+            &self.combatants[attacker.0].character,
+            &self.combatants[attackee.0].character,
+        );
+        Ok(self.record(attacker, attackee, &command))
+    }
+
+    // For attacks the attacker may not have, such as an off-hand attack.
+    fn resolve_optional<F>(
+        &mut self,
+        attacker: CombatantId,
+        attackee: CombatantId,
+        attack: F,
+    ) -> Result<Option<CombatEvent>, EncounterError>
+    where
+        F: FnOnce(&Character, &Character) -> Option<AttackCommand>,
+    {
+        self.check_attack(attacker, attackee)?;
+
+        let command = attack(
+            &self.combatants[attacker.0].character,
+            &self.combatants[attackee.0].character,
+        );
+        Ok(command.map(|command| self.record(attacker, attackee, &command)))
+    }
+
+    fn record(
+        &mut self,
+        attacker: CombatantId,
+        attackee: CombatantId,
+        command: &AttackCommand,
+    ) -> CombatEvent {
+        let event = {
+            let (attacker, attackee) = self.characters_mut(attacker, attackee);
+            resolve_combat(command, attacker, attackee)
+        };
+
+        self.log.push(self.round, attacker, attackee, event.clone());
+        event
+    }
+
+    pub fn winner(&self) -> Option<Side> {
+        let heroes_standing = self.side_standing(Side::Heroes);
+        let monsters_standing = self.side_standing(Side::Monsters);
+
+        match (heroes_standing, monsters_standing) {
+            (true, false) => Some(Side::Heroes),
+            (false, true) => Some(Side::Monsters),
+            _ => None,
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        !self.side_standing(Side::Heroes) || !self.side_standing(Side::Monsters)
+    }
+
+    pub fn defeated(&self, side: Side) -> Vec<&Character> {
+        self.combatants
+            .iter()
+            .filter(|combatant| combatant.side == side && combatant.character.is_dead())
+            .map(|combatant| &combatant.character)
+            .collect()
+    }
+
+    fn is_alive(&self, id: CombatantId) -> bool {
+        !self.combatants[id.0].character.is_dead()
+    }
+
+    fn side_standing(&self, side: Side) -> bool {
+        self.combatants
+            .iter()
+            .any(|combatant| combatant.side == side && !combatant.character.is_dead())
+    }
+
+    // The ids must be distinct and in range.
+    fn characters_mut(
+        &mut self,
+        first: CombatantId,
+        second: CombatantId,
+    ) -> (&mut Character, &mut Character) {
+        match first.0.cmp(&second.0) {
+            Ordering::Less => {
+                let (head, tail) = self.combatants.split_at_mut(second.0);
+                (&mut head[first.0].character, &mut tail[0].character)
+            }
+            _ => {
+                let (head, tail) = self.combatants.split_at_mut(first.0);
+                (&mut tail[0].character, &mut head[second.0].character)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Class;
+    use conditions::Condition;
+    use consumables::Consumable;
+    use dice::LoadedDice;
+    use inventory::{InventoryError, Item};
+    use two_weapon::TwoWeaponFighting;
+    use weapons::Weapon;
+
+    fn roll(dice_roll: u32) -> LoadedDice {
+        LoadedDice::new(vec![dice_roll]).unwrap()
+    }
+
+    #[test]
+    fn initiative_orders_combatants_by_roll_plus_dexterity() {
+        let mut encounter = Encounter::new();
+        let slow = encounter.add(Character::new(Class::Fighter), Side::Heroes);
+        let mut rogue = Character::new(Class::Rogue);
+        rogue.dexterity = 14;
+        let quick = encounter.add(rogue, Side::Monsters);
+
+        encounter.roll_initiative(&mut LoadedDice::new(vec![12, 1, 10, 1]).unwrap());
+
+        assert_eq!(12, encounter.combatant(slow).unwrap().initiative.total());
+        assert_eq!(12, encounter.combatant(quick).unwrap().initiative.total());
+        assert_eq!(&[quick, slow], encounter.turn_order());
+        assert_eq!(Some(quick), encounter.current());
+        assert_eq!(1, encounter.round());
+    }
+
+    #[test]
+    fn initiative_ties_fall_back_to_a_roll_off() {
+        let mut encounter = Encounter::new();
+        let first = encounter.add(Character::new(Class::Fighter), Side::Heroes);
+        let second = encounter.add(Character::new(Class::Fighter), Side::Monsters);
+
+        encounter.roll_initiative(&mut LoadedDice::new(vec![15, 3, 15, 17]).unwrap());
+
+        assert_eq!(&[second, first], encounter.turn_order());
+    }
+
+    #[test]
+    fn turns_advance_through_rounds_and_skip_the_dead() {
+        let mut encounter = Encounter::new();
+        let fighter = encounter.add(Character::new(Class::Fighter), Side::Heroes);
+        let goblin = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+        let orc = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+
+        encounter.roll_initiative(&mut LoadedDice::new(vec![20, 1, 15, 1, 10, 1]).unwrap());
+        assert_eq!(Some(fighter), encounter.current());
+
+        assert_eq!(Some(goblin), encounter.next_turn());
+        assert_eq!(Some(orc), encounter.next_turn());
+        assert_eq!(Some(fighter), encounter.next_turn());
+        assert_eq!(2, encounter.round());
+
+        encounter.combatant_mut(goblin).unwrap().character.damage = 10;
+        assert_eq!(Some(orc), encounter.next_turn());
+    }
+
+    #[test]
+    fn late_arrivals_act_last_in_the_round() {
+        let mut encounter = Encounter::new();
+        let fighter = encounter.add(Character::new(Class::Fighter), Side::Heroes);
+        let goblin = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+        encounter.roll_initiative(&mut LoadedDice::new(vec![20, 1, 15, 1]).unwrap());
+
+        let paladin = encounter.add(Character::new(Class::Paladin), Side::Heroes);
+        let orc = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+        assert_eq!(&[fighter, goblin, paladin, orc], encounter.turn_order());
+
+        encounter.combatant_mut(fighter).unwrap().character.damage = 10;
+        encounter.combatant_mut(goblin).unwrap().character.damage = 10;
+        assert_eq!(Some(paladin), encounter.next_turn());
+        assert_eq!(Some(orc), encounter.next_turn());
+        assert_eq!(Some(paladin), encounter.next_turn());
+    }
+
+    #[test]
+    fn conditions_tick_down_at_the_start_of_each_round() {
+        let mut encounter = Encounter::new();
+        let fighter = encounter.add(Character::new(Class::Fighter), Side::Heroes);
+        let goblin = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+        encounter
+            .combatant_mut(goblin)
+            .unwrap()
+            .character
+            .conditions
+            .add_for(Condition::Stunned, 1);
+
+        encounter.roll_initiative(&mut LoadedDice::new(vec![20, 1, 1, 1]).unwrap());
+        assert_eq!(Some(goblin), encounter.next_turn());
+        assert!(encounter.combatant(goblin).unwrap().character.conditions.has(Condition::Stunned));
+
+        assert_eq!(Some(fighter), encounter.next_turn());
+        assert!(!encounter.combatant(goblin).unwrap().character.conditions.has(Condition::Stunned));
+    }
+
+    #[test]
+    fn two_weapon_fighters_get_an_off_hand_attack() {
+        let mut encounter = Encounter::new();
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.equip_off_hand(Weapon::dagger()).unwrap();
+        let fighter = encounter.add(fighter, Side::Heroes);
+        let goblin = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+        encounter.roll_initiative(&mut LoadedDice::new(vec![20, 1, 1, 1]).unwrap());
+
+        assert_eq!(Ok(None), encounter.off_hand_attack(fighter, goblin, &mut roll(20)));
+
+        encounter
+            .combatant_mut(fighter)
+            .unwrap()
+            .character
+            .two_weapon_fighting = Some(TwoWeaponFighting::default());
+        encounter.attack(fighter, goblin, &mut roll(20)).unwrap();
+        encounter.off_hand_attack(fighter, goblin, &mut roll(20)).unwrap();
+
+        assert_eq!(2, encounter.log().round(1).len());
+    }
+
+    #[test]
+    fn the_off_hand_attacks_once_per_round() {
+        let mut encounter = Encounter::new();
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.two_weapon_fighting = Some(TwoWeaponFighting::default());
+        fighter.equip_off_hand(Weapon::dagger()).unwrap();
+        let fighter = encounter.add(fighter, Side::Heroes);
+        let goblin = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+        encounter.roll_initiative(&mut LoadedDice::new(vec![20, 1, 1, 1]).unwrap());
+
+        encounter.off_hand_attack(fighter, goblin, &mut roll(1)).unwrap();
+        assert_eq!(
+            Err(EncounterError::OffHandUsed(fighter)),
+            encounter.off_hand_attack(fighter, goblin, &mut roll(1))
+        );
+
+        encounter.next_turn();
+        encounter.next_turn();
+        assert!(encounter.off_hand_attack(fighter, goblin, &mut roll(1)).unwrap().is_some());
+        assert_eq!(1, encounter.log().round(1).len());
+    }
+
+    #[test]
+    fn thrown_items_are_logged_and_follow_the_turn_order() {
+        let mut encounter = Encounter::new();
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.inventory.add(Item::Consumable(Consumable::alchemists_fire()), 1);
+        let fighter = encounter.add(fighter, Side::Heroes);
+        let goblin = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+        encounter.roll_initiative(&mut LoadedDice::new(vec![20, 1, 1, 1]).unwrap());
+
+        assert_eq!(
+            Err(EncounterError::NotTheirTurn {
+                attacker: goblin,
+                current: fighter,
+            }),
+            encounter.throw_item(goblin, fighter, "alchemists-fire", 10, &mut roll(15))
+        );
+
+        let dice = &mut LoadedDice::new(vec![15, 4]).unwrap();
+        let event = encounter.throw_item(fighter, goblin, "alchemists-fire", 10, dice).unwrap();
+        assert_eq!(4, event.damage_dealt);
+        assert_eq!(Some(&event), encounter.log().round(1).first().cloned());
+
+        assert_eq!(
+            Err(EncounterError::Consumable(ConsumableError::Inventory(
+                InventoryError::NotCarried {
+                    id: "alchemists-fire".to_string(),
+                }
+            ))),
+            encounter.throw_item(fighter, goblin, "alchemists-fire", 10, &mut roll(15))
+        );
+        assert_eq!(1, encounter.log().round(1).len());
+    }
+
+    #[test]
+    fn the_encounter_ends_when_a_side_is_defeated() {
+        let mut encounter = Encounter::new();
+        let fighter = encounter.add(Character::new(Class::Fighter), Side::Heroes);
+        let goblin = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+        encounter.combatant_mut(goblin).unwrap().character.damage = 9;
+
+        encounter.roll_initiative(&mut LoadedDice::new(vec![20, 1, 1, 1]).unwrap());
+        assert!(!encounter.is_over());
+
+        let event = encounter.attack(fighter, goblin, &mut roll(15)).unwrap();
+
+        assert!(event.killing_blow);
+        assert_eq!(Some(&event), encounter.log().round(1).first().cloned());
+        let entry = encounter.log().iter().next().unwrap();
+        assert_eq!((fighter, goblin), (entry.attacker, entry.defender));
+        assert!(encounter.is_over());
+        assert_eq!(Some(Side::Heroes), encounter.winner());
+        assert_eq!(None, encounter.next_turn());
+    }
+
+    #[test]
+    fn weapon_damage_is_rolled() {
+        let mut encounter = Encounter::new();
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.equip(Weapon::longsword());
+        let fighter = encounter.add(fighter, Side::Heroes);
+        let goblin = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+        encounter.roll_initiative(&mut LoadedDice::new(vec![20, 1, 1, 1]).unwrap());
+
+        let event = encounter
+            .attack(fighter, goblin, &mut LoadedDice::new(vec![15, 7]).unwrap())
+            .unwrap();
+
+        assert_eq!(15, event.dice_roll);
+        assert_eq!(7, event.damage_dealt);
+    }
+
+    #[test]
+    fn only_the_current_combatant_may_attack() {
+        let mut encounter = Encounter::new();
+        let fighter = encounter.add(Character::new(Class::Fighter), Side::Heroes);
+        let goblin = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+
+        assert_eq!(
+            Err(EncounterError::NotStarted),
+            encounter.attack(fighter, goblin, &mut roll(15))
+        );
+
+        encounter.roll_initiative(&mut LoadedDice::new(vec![20, 1, 1, 1]).unwrap());
+
+        assert_eq!(
+            Err(EncounterError::NotTheirTurn {
+                attacker: goblin,
+                current: fighter,
+            }),
+            encounter.attack(goblin, fighter, &mut roll(15))
+        );
+        assert_eq!(
+            Err(EncounterError::TargetsItself(fighter)),
+            encounter.attack(fighter, fighter, &mut roll(15))
+        );
+        assert_eq!(
+            Err(EncounterError::UnknownCombatant(CombatantId(7))),
+            encounter.attack(fighter, CombatantId(7), &mut roll(15))
+        );
+        assert!(encounter.combatant(CombatantId(7)).is_none());
+        assert!(encounter.log().round(1).is_empty());
+    }
+
+    #[test]
+    fn the_dead_cannot_attack_or_be_attacked() {
+        let mut encounter = Encounter::new();
+        let fighter = encounter.add(Character::new(Class::Fighter), Side::Heroes);
+        let goblin = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+        let orc = encounter.add(Character::new(Class::Commoner), Side::Monsters);
+        encounter.roll_initiative(&mut LoadedDice::new(vec![20, 1, 15, 1, 10, 1]).unwrap());
+
+        encounter.combatant_mut(goblin).unwrap().character.damage = 10;
+        assert_eq!(
+            Err(EncounterError::Dead(goblin)),
+            encounter.attack(fighter, goblin, &mut roll(15))
+        );
+        assert_eq!(
+            Err(EncounterError::NotTheirTurn {
+                attacker: goblin,
+                current: fighter,
+            }),
+            encounter.attack(goblin, fighter, &mut roll(15))
+        );
+
+        assert_eq!(Some(orc), encounter.next_turn());
+        encounter.combatant_mut(orc).unwrap().character.damage = 10;
+        assert_eq!(Err(EncounterError::Over), encounter.attack(orc, fighter, &mut roll(15)));
+    }
+}
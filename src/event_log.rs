@@ -0,0 +1,118 @@
+use std::slice;
+
+use combat::CombatEvent;
+use encounter::CombatantId;
+
+// Names need not be unique, so each entry also records who attacked whom.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LogEntry {
+    pub round: u32,
+    pub attacker: CombatantId,
+    pub defender: CombatantId,
+    pub event: CombatEvent,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct EventLog {
+    entries: Vec<LogEntry>,
+}
+
+impl EventLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(
+        &mut self,
+        round: u32,
+        attacker: CombatantId,
+        defender: CombatantId,
+        event: CombatEvent,
+    ) {
+        self.entries.push(LogEntry {
+            round,
+            attacker,
+            defender,
+            event,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn round(&self, round: u32) -> Vec<&CombatEvent> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.round == round)
+            .map(|entry| &entry.event)
+            .collect()
+    }
+
+    // Feeds every logged entry, in the order it happened, to `handler`.
+    pub fn replay<F>(&self, mut handler: F)
+    where
+        F: FnMut(&LogEntry),
+    {
+        for entry in &self.entries {
+            handler(entry);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a EventLog {
+    type Item = &'a LogEntry;
+    type IntoIter = slice::Iter<'a, LogEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::{Character, Class};
+    use combat::resolve_combat;
+
+    fn event(dice_roll: u32) -> CombatEvent {
+        let mut attacker = Character::new(Class::Commoner);
+        let mut attackee = Character::new(Class::Commoner);
+        resolve_combat(&attacker.attack(&attackee, dice_roll), &mut attacker, &mut attackee)
+    }
+
+    const FIRST: CombatantId = CombatantId(0);
+    const SECOND: CombatantId = CombatantId(1);
+
+    #[test]
+    fn events_are_grouped_by_round() {
+        let mut log = EventLog::new();
+        log.push(1, FIRST, SECOND, event(5));
+        log.push(1, SECOND, FIRST, event(15));
+        log.push(2, FIRST, SECOND, event(12));
+
+        assert_eq!(3, log.len());
+        assert_eq!(2, log.round(1).len());
+        assert_eq!(12, log.round(2)[0].dice_roll);
+    }
+
+    #[test]
+    fn replay_visits_events_in_order() {
+        let mut log = EventLog::new();
+        log.push(1, FIRST, SECOND, event(5));
+        log.push(2, SECOND, FIRST, event(15));
+
+        let mut replayed = Vec::new();
+        log.replay(|entry| replayed.push((entry.round, entry.attacker, entry.event.dice_roll)));
+
+        assert_eq!(vec![(1, FIRST, 5), (2, SECOND, 15)], replayed);
+    }
+}
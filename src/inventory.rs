@@ -0,0 +1,452 @@
+use std::slice;
+
+use armor::{Armor, ArmorError};
+use character::{Ability, Character};
+use combat::DiceRollModifier;
+use consumables::Consumable;
+use magic_items::MagicItem;
+use shields::{Shield, ShieldError};
+use weapons::Weapon;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Item {
+    Weapon(Weapon),
+    Armor(Armor),
+    Shield(Shield),
+    MagicItem(MagicItem),
+    Consumable(Consumable),
+    Gear { id: String, name: String, weight: u32 },
+}
+
+// The id an item gets when it is not loaded from a catalog: its name in lower
+// case, with words joined by hyphens.
+pub(crate) fn item_id(name: &str) -> String {
+    name.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|character| character.is_alphanumeric() || *character == '-')
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+// An enhanced item gets its own id so it never shares a stack, or a lookup,
+// with the plain item it was made from.
+pub(crate) fn enhanced_id(id: &str, magic_bonus: DiceRollModifier) -> String {
+    match magic_bonus {
+        0 => id.to_string(),
+        bonus if bonus > 0 => format!("{}-plus-{}", id, bonus),
+        bonus => format!("{}-minus-{}", id, -bonus),
+    }
+}
+
+impl Item {
+    pub fn gear(name: &str, weight: u32) -> Self {
+        Item::Gear {
+            id: item_id(name),
+            name: name.to_string(),
+            weight,
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        match *self {
+            Item::Weapon(ref weapon) => &weapon.id,
+            Item::Armor(ref armor) => &armor.id,
+            Item::Shield(ref shield) => &shield.id,
+            Item::MagicItem(ref item) => &item.id,
+            Item::Consumable(ref consumable) => &consumable.id,
+            Item::Gear { ref id, .. } => id,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match *self {
+            Item::Weapon(ref weapon) => &weapon.name,
+            Item::Armor(ref armor) => &armor.name,
+            Item::Shield(ref shield) => &shield.name,
+            Item::MagicItem(ref item) => &item.name,
+            Item::Consumable(ref consumable) => &consumable.name,
+            Item::Gear { ref name, .. } => name,
+        }
+    }
+
+    pub fn weight(&self) -> u32 {
+        match *self {
+            Item::Weapon(ref weapon) => weapon.weight,
+            Item::Armor(ref armor) => armor.weight,
+            Item::Shield(ref shield) => shield.weight,
+            Item::MagicItem(ref item) => item.weight,
+            Item::Consumable(ref consumable) => consumable.weight,
+            Item::Gear { weight, .. } => weight,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ItemStack {
+    pub item: Item,
+    pub quantity: u32,
+}
+
+impl ItemStack {
+    pub fn weight(&self) -> u32 {
+        self.item.weight() * self.quantity
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum InventoryError {
+    NotCarried { id: String },
+    NotEnough { id: String, requested: u32, carried: u32 },
+    NotEquippable { id: String },
+    Armor(ArmorError),
+    Shield(ShieldError),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Inventory {
+    stacks: Vec<ItemStack>,
+}
+
+impl Inventory {
+    // Identical items share a stack. Stacks are looked up by item id.
+    pub fn add(&mut self, item: Item, quantity: u32) {
+        if quantity == 0 {
+            return;
+        }
+        match self.stacks.iter_mut().find(|stack| stack.item == item) {
+            Some(stack) => stack.quantity += quantity,
+            None => self.stacks.push(ItemStack { item, quantity }),
+        }
+    }
+
+    pub fn remove(&mut self, id: &str, quantity: u32) -> Result<ItemStack, InventoryError> {
+        let index = self
+            .stacks
+            .iter()
+            .position(|stack| stack.item.id() == id)
+            .ok_or_else(|| InventoryError::NotCarried { id: id.to_string() })?;
+
+        let carried = self.stacks[index].quantity;
+        if quantity > carried {
+            return Err(InventoryError::NotEnough {
+                id: id.to_string(),
+                requested: quantity,
+                carried,
+            });
+        }
+
+        if quantity == carried {
+            Ok(self.stacks.remove(index))
+        } else {
+            self.stacks[index].quantity -= quantity;
+            Ok(ItemStack {
+                item: self.stacks[index].item.clone(),
+                quantity,
+            })
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Item> {
+        self.stacks
+            .iter()
+            .find(|stack| stack.item.id() == id)
+            .map(|stack| &stack.item)
+    }
+
+    pub fn quantity(&self, id: &str) -> u32 {
+        self.stacks
+            .iter()
+            .filter(|stack| stack.item.id() == id)
+            .map(|stack| stack.quantity)
+            .sum()
+    }
+
+    pub fn weight(&self) -> u32 {
+        self.stacks.iter().map(|stack| stack.weight()).sum()
+    }
+
+    pub fn len(&self) -> usize {
+        self.stacks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stacks.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, ItemStack> {
+        self.stacks.iter()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+pub enum Encumbrance {
+    Unencumbered,
+    Encumbered,
+    HeavilyEncumbered,
+    Overloaded,
+}
+
+impl Encumbrance {
+    pub fn max_dexterity_bonus(&self) -> Option<DiceRollModifier> {
+        match *self {
+            Encumbrance::Unencumbered => None,
+            Encumbrance::Encumbered => Some(3),
+            Encumbrance::HeavilyEncumbered => Some(1),
+            Encumbrance::Overloaded => Some(0),
+        }
+    }
+
+    pub fn speed(&self, base_speed: u32) -> u32 {
+        match *self {
+            Encumbrance::Unencumbered => base_speed,
+            Encumbrance::Encumbered => base_speed.saturating_sub(10),
+            Encumbrance::HeavilyEncumbered => base_speed.saturating_sub(20),
+            Encumbrance::Overloaded => 0,
+        }
+    }
+}
+
+const BASE_SPEED: u32 = 30;
+
+impl Character {
+    // Equipped items are carried too.
+    pub fn carried_weight(&self) -> u32 {
+        self.inventory.weight() +
+            self.main_hand.as_ref().map_or(0, |weapon| weapon.weight) +
+            self.off_hand.as_ref().map_or(0, |weapon| weapon.weight) +
+            self.armor.as_ref().map_or(0, |armor| armor.weight) +
+            self.shield.as_ref().map_or(0, |shield| shield.weight) +
+            self.magic_items.iter().map(|item| item.weight).sum::<u32>()
+    }
+
+    pub fn carrying_capacity(&self) -> u32 {
+        self.ability_score(Ability::Strength) * 10
+    }
+
+    // Carrying more than a third of capacity encumbers, more than two thirds
+    // heavily encumbers.
+    pub fn encumbrance(&self) -> Encumbrance {
+        let carried_weight = self.carried_weight();
+        let carrying_capacity = self.carrying_capacity();
+
+        if carried_weight > carrying_capacity {
+            Encumbrance::Overloaded
+        } else if carried_weight * 3 > carrying_capacity * 2 {
+            Encumbrance::HeavilyEncumbered
+        } else if carried_weight * 3 > carrying_capacity {
+            Encumbrance::Encumbered
+        } else {
+            Encumbrance::Unencumbered
+        }
+    }
+
+    pub fn speed(&self) -> u32 {
+        self.encumbrance().speed(BASE_SPEED)
+    }
+
+    pub fn transfer_item(
+        &mut self,
+        recipient: &mut Character,
+        id: &str,
+        quantity: u32,
+    ) -> Result<(), InventoryError> {
+        let stack = self.inventory.remove(id, quantity)?;
+        recipient.inventory.add(stack.item, stack.quantity);
+        Ok(())
+    }
+
+    // Whatever the item replaces goes back into the inventory.
+    pub fn equip_from_inventory(&mut self, id: &str) -> Result<(), InventoryError> {
+        match self.inventory.get(id) {
+            None => return Err(InventoryError::NotCarried { id: id.to_string() }),
+            Some(Item::Gear { .. }) | Some(Item::Consumable(_)) => {
+                return Err(InventoryError::NotEquippable { id: id.to_string() })
+            }
+            Some(Item::Armor(armor)) if !armor.can_be_worn_by(self) => {
+                return Err(InventoryError::Armor(ArmorError::Restricted {
+                    armor: armor.name.clone(),
+                    class: self.class,
+                    race: self.race,
+                }))
+            }
+            Some(Item::Shield(shield)) => self
+                .check_off_hand_free(shield)
+                .map_err(InventoryError::Shield)?,
+            Some(_) => {}
+        }
+
+        let replaced = match self.inventory.remove(id, 1)?.item {
+            Item::Weapon(weapon) => self.equip(weapon).map(Item::Weapon),
+            Item::Armor(armor) => self
+                .wear(armor)
+                .map_err(InventoryError::Armor)?
+                .map(Item::Armor),
+            Item::Shield(shield) => self
+                .equip_shield(shield)
+                .map_err(InventoryError::Shield)?
+                .map(Item::Shield),
+            Item::MagicItem(item) => {
+                self.put_on(item);
+                None
+            }
+            Item::Gear { .. } | Item::Consumable(_) => unreachable!(),
+        };
+
+        if let Some(item) = replaced {
+            self.inventory.add(item, 1);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Class;
+
+    #[test]
+    fn identical_items_stack() {
+        let mut inventory = Inventory::default();
+        inventory.add(Item::gear("torch", 1), 3);
+        inventory.add(Item::gear("torch", 1), 2);
+        inventory.add(Item::Weapon(Weapon::dagger()), 1);
+
+        assert_eq!(2, inventory.len());
+        assert_eq!(5, inventory.quantity("torch"));
+        assert_eq!(6, inventory.weight());
+    }
+
+    #[test]
+    fn items_are_looked_up_by_id() {
+        let mut inventory = Inventory::default();
+        let magic_longsword = Weapon::longsword().with_magic_bonus(1);
+        inventory.add(Item::Weapon(Weapon::longsword()), 1);
+        inventory.add(Item::Weapon(magic_longsword.clone()), 1);
+        inventory.add(Item::Consumable(Consumable::alchemists_fire()), 1);
+
+        assert_eq!(3, inventory.len());
+        assert_eq!(1, inventory.quantity("longsword"));
+        assert_eq!(Some(&Item::Weapon(Weapon::longsword())), inventory.get("longsword"));
+        assert_eq!(Some(&Item::Weapon(magic_longsword)), inventory.get("longsword-plus-1"));
+        assert_eq!(1, inventory.quantity("alchemists-fire"));
+        assert_eq!(0, inventory.quantity("alchemist's fire"));
+    }
+
+    #[test]
+    fn removing_more_than_is_carried_fails() {
+        let mut inventory = Inventory::default();
+        inventory.add(Item::gear("torch", 1), 2);
+
+        assert_eq!(
+            Err(InventoryError::NotEnough {
+                id: "torch".to_string(),
+                requested: 3,
+                carried: 2,
+            }),
+            inventory.remove("torch", 3)
+        );
+        assert_eq!(1, inventory.remove("torch", 1).unwrap().quantity);
+        assert_eq!(1, inventory.quantity("torch"));
+        assert_eq!(
+            Err(InventoryError::NotCarried {
+                id: "rope".to_string(),
+            }),
+            inventory.remove("rope", 1)
+        );
+    }
+
+    #[test]
+    fn items_can_be_handed_to_another_character() {
+        let mut giver = Character::new(Class::Fighter);
+        let mut recipient = Character::new(Class::Rogue);
+        giver.inventory.add(Item::gear("ration", 1), 4);
+
+        giver.transfer_item(&mut recipient, "ration", 3).unwrap();
+
+        assert_eq!(1, giver.inventory.quantity("ration"));
+        assert_eq!(3, recipient.inventory.quantity("ration"));
+    }
+
+    #[test]
+    fn equipping_from_the_inventory_stows_the_replaced_item() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.equip(Weapon::dagger());
+        fighter.inventory.add(Item::Weapon(Weapon::longsword()), 1);
+
+        fighter.equip_from_inventory("longsword").unwrap();
+
+        assert_eq!(Some(Weapon::longsword()), fighter.main_hand);
+        assert_eq!(Some(&Item::Weapon(Weapon::dagger())), fighter.inventory.get("dagger"));
+        assert_eq!(0, fighter.inventory.quantity("longsword"));
+    }
+
+    #[test]
+    fn restricted_armor_stays_in_the_inventory() {
+        let mut rogue = Character::new(Class::Rogue);
+        rogue.inventory.add(Item::Armor(Armor::plate()), 1);
+
+        assert!(rogue.equip_from_inventory("plate").is_err());
+        assert_eq!(1, rogue.inventory.quantity("plate"));
+        assert_eq!(None, rogue.armor);
+    }
+
+    #[test]
+    fn a_shield_stays_in_the_inventory_while_the_off_hand_is_full() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.equip_off_hand(Weapon::dagger()).unwrap();
+        fighter.inventory.add(Item::Shield(Shield::buckler()), 1);
+
+        assert!(fighter.equip_from_inventory("buckler").is_err());
+        assert_eq!(1, fighter.inventory.quantity("buckler"));
+        assert_eq!(None, fighter.shield);
+    }
+
+    #[test]
+    fn plain_gear_cannot_be_equipped() {
+        let mut character = Character::new(Class::Commoner);
+        character.inventory.add(Item::gear("rope", 10), 1);
+
+        assert_eq!(
+            Err(InventoryError::NotEquippable {
+                id: "rope".to_string(),
+            }),
+            character.equip_from_inventory("rope")
+        );
+    }
+
+    #[test]
+    fn capacity_follows_strength() {
+        let mut character = Character::new(Class::Fighter);
+        character.strength = 12;
+
+        assert_eq!(120, character.carrying_capacity());
+        assert_eq!(Encumbrance::Unencumbered, character.encumbrance());
+        assert_eq!(30, character.speed());
+
+        character.wear(Armor::plate()).unwrap();
+        assert_eq!(Encumbrance::Encumbered, character.encumbrance());
+        assert_eq!(20, character.speed());
+
+        character.inventory.add(Item::gear("anvil", 40), 1);
+        assert_eq!(Encumbrance::HeavilyEncumbered, character.encumbrance());
+
+        character.inventory.add(Item::gear("anvil", 40), 1);
+        assert_eq!(Encumbrance::Overloaded, character.encumbrance());
+        assert_eq!(0, character.speed());
+    }
+
+    #[test]
+    fn encumbrance_caps_the_dexterity_bonus() {
+        let mut character = Character::new(Class::Rogue);
+        character.dexterity = 18;
+        character.inventory.add(Item::gear("anvil", 40), 1);
+
+        assert_eq!(Encumbrance::Encumbered, character.encumbrance());
+        assert_eq!(3, character.defense_dexterity_modifier());
+    }
+}
@@ -0,0 +1,138 @@
+use character::{Character, Class};
+use combat::DiceRollModifier;
+
+pub type LevelUpObserver = Box<dyn FnMut(&LevelUpReport)>;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Feature {
+    AbilityScoreIncrease,
+    BonusFeat,
+    SneakAttack,
+    Evasion,
+    DivineGrace,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LevelUpReport {
+    pub old_level: u64,
+    pub new_level: u64,
+    pub hit_points_gained: u32,
+    pub attack_bonus: DiceRollModifier,
+    pub unlocked_features: Vec<Feature>,
+}
+
+fn features_at(class: Class, level: u64) -> Vec<Feature> {
+    let mut features = Vec::new();
+
+    if level.is_multiple_of(4) {
+        features.push(Feature::AbilityScoreIncrease);
+    }
+
+    match class {
+        Class::Fighter if level.is_multiple_of(2) => features.push(Feature::BonusFeat),
+        Class::Rogue if !level.is_multiple_of(2) => features.push(Feature::SneakAttack),
+        Class::Monk if level == 2 => features.push(Feature::Evasion),
+        Class::Paladin if level == 2 => features.push(Feature::DivineGrace),
+        _ => {}
+    }
+
+    features
+}
+
+impl Character {
+    pub fn gain_experience(&mut self, experience_points: u64) -> Option<LevelUpReport> {
+        let old_level = self.level();
+        let old_max_hit_points = self.max_hit_points();
+
+        self.experience_points += experience_points;
+
+        let new_level = self.level();
+        if new_level == old_level {
+            return None;
+        }
+
+        self.record_hit_point_rolls();
+
+        let report = LevelUpReport {
+            old_level,
+            new_level,
+            hit_points_gained: self.max_hit_points() - old_max_hit_points,
+            attack_bonus: self.level_modifier(),
+            unlocked_features: (old_level + 1..=new_level)
+                .flat_map(|level| features_at(self.class, level))
+                .collect(),
+        };
+
+        for observer in &mut self.level_up_observers {
+            observer(&report);
+        }
+
+        Some(report)
+    }
+
+    pub fn on_level_up<F>(&mut self, observer: F)
+    where
+        F: FnMut(&LevelUpReport) + 'static,
+    {
+        self.level_up_observers.push(Box::new(observer));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn gaining_experience_without_levelling_reports_nothing() {
+        let mut character = Character::new(Class::Commoner);
+
+        assert_eq!(None, character.gain_experience(999));
+        assert_eq!(999, character.experience_points);
+    }
+
+    #[test]
+    fn crossing_a_level_threshold_produces_a_report() {
+        let mut character = Character::new(Class::Fighter);
+        character.experience_points = 990;
+
+        let report = character.gain_experience(10).unwrap();
+
+        assert_eq!(1, report.old_level);
+        assert_eq!(2, report.new_level);
+        assert_eq!(10, report.hit_points_gained);
+        assert_eq!(2, report.attack_bonus);
+        assert_eq!(vec![Feature::BonusFeat], report.unlocked_features);
+    }
+
+    #[test]
+    fn skipping_levels_collects_every_unlocked_feature() {
+        let mut character = Character::new(Class::Commoner);
+
+        let report = character.gain_experience(3000).unwrap();
+
+        assert_eq!(1, report.old_level);
+        assert_eq!(4, report.new_level);
+        assert_eq!(15, report.hit_points_gained);
+        assert_eq!(
+            vec![Feature::AbilityScoreIncrease],
+            report.unlocked_features
+        );
+    }
+
+    #[test]
+    fn level_up_observers_are_notified() {
+        let mut character = Character::new(Class::Monk);
+        let announced = Rc::new(RefCell::new(Vec::new()));
+
+        let log = announced.clone();
+        character.on_level_up(move |report| log.borrow_mut().push(report.new_level));
+
+        character.gain_experience(500);
+        character.gain_experience(500);
+        character.gain_experience(1000);
+
+        assert_eq!(vec![2, 3], *announced.borrow());
+    }
+}
@@ -1,3 +1,32 @@
-#![allow(dead_code)]
-mod character;
-mod combat;
+extern crate rand;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+extern crate toml;
+
+pub mod advantage;
+pub mod armor;
+pub mod breakdown;
+pub mod catalog;
+pub mod character;
+pub mod combat;
+pub mod conditions;
+pub mod consumables;
+pub mod currency;
+pub mod damage;
+pub mod dice;
+pub mod encounter;
+pub mod event_log;
+pub mod inventory;
+pub mod leveling;
+pub mod magic_items;
+pub mod maneuvers;
+pub mod merchants;
+pub mod proficiency;
+pub mod ranged;
+pub mod saving_throws;
+pub mod shields;
+pub mod treasure;
+pub mod two_weapon;
+pub mod weapons;
@@ -0,0 +1,188 @@
+use std::cmp;
+
+use character::{Ability, Alignment, Character};
+use combat::DiceRollModifier;
+use inventory::item_id;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BonusTarget {
+    AbilityScore(Ability),
+    ArmorClass,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MagicBonus {
+    pub target: BonusTarget,
+    pub value: DiceRollModifier,
+    // Only applies against opponents of this alignment.
+    pub against: Option<Alignment>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MagicItem {
+    pub id: String,
+    pub name: String,
+    pub bonuses: Vec<MagicBonus>,
+    pub weight: u32,
+}
+
+impl MagicItem {
+    pub fn new(name: &str) -> Self {
+        Self {
+            id: item_id(name),
+            name: name.to_string(),
+            bonuses: Vec::new(),
+            weight: 0,
+        }
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_bonus(mut self, target: BonusTarget, value: DiceRollModifier) -> Self {
+        self.bonuses.push(MagicBonus {
+            target,
+            value,
+            against: None,
+        });
+        self
+    }
+
+    pub fn with_bonus_against(
+        mut self,
+        target: BonusTarget,
+        value: DiceRollModifier,
+        alignment: Alignment,
+    ) -> Self {
+        self.bonuses.push(MagicBonus {
+            target,
+            value,
+            against: Some(alignment),
+        });
+        self
+    }
+
+    pub fn ring_of_protection(bonus: DiceRollModifier) -> Self {
+        MagicItem::new("ring of protection").with_bonus(BonusTarget::ArmorClass, bonus)
+    }
+
+    pub fn belt_of_giant_strength(bonus: DiceRollModifier) -> Self {
+        MagicItem::new("belt of giant strength")
+            .with_bonus(BonusTarget::AbilityScore(Ability::Strength), bonus)
+            .with_weight(1)
+    }
+
+    pub fn amulet_of_health(bonus: DiceRollModifier) -> Self {
+        MagicItem::new("amulet of health")
+            .with_bonus(BonusTarget::AbilityScore(Ability::Constitution), bonus)
+    }
+
+    pub fn amulet_of_protection_from_evil() -> Self {
+        MagicItem::new("amulet of protection from evil").with_bonus_against(
+            BonusTarget::ArmorClass,
+            2,
+            Alignment::Evil,
+        )
+    }
+}
+
+impl Character {
+    pub fn put_on(&mut self, item: MagicItem) {
+        self.magic_items.push(item);
+    }
+
+    pub fn take_off(&mut self, id: &str) -> Option<MagicItem> {
+        let index = self.magic_items.iter().position(|item| item.id == id)?;
+        Some(self.magic_items.remove(index))
+    }
+
+    // Magic bonuses to the same target do not stack; only the highest one
+    // applies, so two rings of protection count once.
+    pub fn magic_bonus(
+        &self,
+        target: BonusTarget,
+        opponent: Option<Alignment>,
+    ) -> DiceRollModifier {
+        let highest = self
+            .magic_items
+            .iter()
+            .flat_map(|item| item.bonuses.iter())
+            .filter(|bonus| bonus.target == target)
+            .filter(|bonus| bonus.against.is_none_or(|alignment| Some(alignment) == opponent))
+            .map(|bonus| bonus.value)
+            .max();
+
+        cmp::max(highest.unwrap_or(0), 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Class;
+
+    #[test]
+    fn a_ring_of_protection_adds_to_armor_class() {
+        let mut character = Character::new(Class::Commoner);
+        character.put_on(MagicItem::ring_of_protection(2));
+
+        assert_eq!(12, character.armor_class());
+        assert_eq!(
+            Some(MagicItem::ring_of_protection(2)),
+            character.take_off("ring-of-protection")
+        );
+        assert_eq!(10, character.armor_class());
+    }
+
+    #[test]
+    fn two_rings_of_protection_do_not_stack() {
+        let mut character = Character::new(Class::Commoner);
+        character.put_on(MagicItem::ring_of_protection(1));
+        character.put_on(MagicItem::ring_of_protection(2));
+
+        assert_eq!(12, character.armor_class());
+    }
+
+    #[test]
+    fn a_belt_of_giant_strength_raises_the_strength_modifier() {
+        let mut character = Character::new(Class::Fighter);
+        character.strength = 16;
+        character.put_on(MagicItem::belt_of_giant_strength(4));
+
+        assert_eq!(20, character.ability_score(Ability::Strength));
+        assert_eq!(5, character.strength_modifier());
+    }
+
+    #[test]
+    fn an_amulet_of_health_raises_hit_points() {
+        let mut character = Character::new(Class::Fighter);
+        character.experience_points = 1000;
+        assert_eq!(20, character.max_hit_points());
+
+        character.put_on(MagicItem::amulet_of_health(4));
+
+        assert_eq!(2, character.constitution_modifier());
+        assert_eq!(24, character.max_hit_points());
+    }
+
+    #[test]
+    fn some_items_only_protect_against_an_alignment() {
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.put_on(MagicItem::amulet_of_protection_from_evil());
+        let mut evil = Character::new(Class::Commoner);
+        evil.alignment = Alignment::Evil;
+        let good = Character::new(Class::Commoner);
+
+        assert_eq!(10, attackee.armor_class());
+        assert_eq!(12, evil.attack(&attackee, 10).armor_class);
+        assert_eq!(10, good.attack(&attackee, 10).armor_class);
+    }
+}
@@ -0,0 +1,250 @@
+use std::cmp;
+
+use character::Character;
+use combat::DiceRollModifier;
+use conditions::Condition;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Maneuver {
+    Trip,
+    Disarm,
+    Grapple,
+    Shove,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ManeuverCheck {
+    pub dice_roll: u32,
+    pub modifier: DiceRollModifier,
+}
+
+impl ManeuverCheck {
+    pub fn total(&self) -> i32 {
+        self.dice_roll as i32 + self.modifier
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ManeuverCommand {
+    pub maneuver: Maneuver,
+    pub attacker_check: ManeuverCheck,
+    pub defender_check: ManeuverCheck,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ManeuverEffect {
+    Tripped,
+    Disarmed,
+    Grappled,
+    Shoved { distance: u32 },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ManeuverResult {
+    pub maneuver: Maneuver,
+    pub attacker_total: i32,
+    pub defender_total: i32,
+    pub effect: Option<ManeuverEffect>,
+}
+
+impl ManeuverResult {
+    pub fn succeeded(&self) -> bool {
+        self.effect.is_some()
+    }
+}
+
+impl ManeuverCommand {
+    // The defender wins ties.
+    pub fn succeeds(&self) -> bool {
+        self.attacker_check.total() > self.defender_check.total()
+    }
+
+    // A shove moves the defender 5 feet, plus 5 more for every 5 points the
+    // attacker's check beat the defender's by.
+    pub fn shove_distance(&self) -> u32 {
+        let margin = self.attacker_check.total() - self.defender_check.total();
+        5 + 5 * (cmp::max(margin, 0) / 5) as u32
+    }
+}
+
+pub fn resolve_maneuver(
+    command: &ManeuverCommand,
+    attacker: &mut Character,
+    defender: &mut Character,
+) -> ManeuverResult {
+    // A defender with nothing in hand has nothing to be disarmed of.
+    let effect = if !command.succeeds() {
+        None
+    } else {
+        match command.maneuver {
+            Maneuver::Trip => {
+                defender.conditions.add(Condition::Prone);
+                Some(ManeuverEffect::Tripped)
+            }
+            Maneuver::Disarm => defender.unequip().map(|weapon| {
+                defender.conditions.add(Condition::Disarmed);
+                defender.dropped_weapon = Some(weapon);
+                ManeuverEffect::Disarmed
+            }),
+            Maneuver::Grapple => {
+                attacker.conditions.add(Condition::Grappled);
+                defender.conditions.add(Condition::Grappled);
+                Some(ManeuverEffect::Grappled)
+            }
+            Maneuver::Shove => Some(ManeuverEffect::Shoved {
+                distance: command.shove_distance(),
+            }),
+        }
+    };
+
+    ManeuverResult {
+        maneuver: command.maneuver,
+        attacker_total: command.attacker_check.total(),
+        defender_total: command.defender_check.total(),
+        effect,
+    }
+}
+
+impl Character {
+    pub fn maneuver_bonus(&self) -> DiceRollModifier {
+        self.level_modifier() + self.strength_modifier() + self.conditions.attack_modifier()
+    }
+
+    pub fn maneuver_defense(&self) -> DiceRollModifier {
+        self.level_modifier() + cmp::max(self.strength_modifier(), self.dexterity_modifier())
+    }
+
+    pub fn maneuver(
+        &self,
+        defender: &Character,
+        maneuver: Maneuver,
+        attacker_roll: u32,
+        defender_roll: u32,
+    ) -> ManeuverCommand {
+        ManeuverCommand {
+            maneuver,
+            attacker_check: ManeuverCheck {
+                dice_roll: attacker_roll,
+                modifier: self.maneuver_bonus(),
+            },
+            defender_check: ManeuverCheck {
+                dice_roll: defender_roll,
+                modifier: defender.maneuver_defense(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Class;
+    use weapons::Weapon;
+
+    #[test]
+    fn a_successful_trip_knocks_the_defender_prone() {
+        let mut attacker = Character::new(Class::Fighter);
+        let mut defender = Character::new(Class::Commoner);
+
+        let command = attacker.maneuver(&defender, Maneuver::Trip, 12, 10);
+        let result = resolve_maneuver(&command, &mut attacker, &mut defender);
+
+        assert_eq!(Some(ManeuverEffect::Tripped), result.effect);
+        assert!(defender.conditions.has(Condition::Prone));
+    }
+
+    #[test]
+    fn the_defender_wins_ties() {
+        let mut attacker = Character::new(Class::Commoner);
+        let mut defender = Character::new(Class::Commoner);
+        defender.equip(Weapon::dagger());
+
+        let command = attacker.maneuver(&defender, Maneuver::Disarm, 10, 10);
+        let result = resolve_maneuver(&command, &mut attacker, &mut defender);
+
+        assert!(!result.succeeded());
+        assert!(!defender.conditions.has(Condition::Disarmed));
+        assert_eq!(Some(Weapon::dagger()), defender.main_hand);
+    }
+
+    #[test]
+    fn a_disarmed_defender_attacks_at_a_penalty() {
+        let mut attacker = Character::new(Class::Fighter);
+        let mut defender = Character::new(Class::Commoner);
+        defender.equip(Weapon::dagger());
+
+        let command = attacker.maneuver(&defender, Maneuver::Disarm, 15, 10);
+        resolve_maneuver(&command, &mut attacker, &mut defender);
+
+        assert!(defender.conditions.has(Condition::Disarmed));
+        assert_eq!(-4, defender.attack(&attacker, 10).condition_attack_modifier);
+    }
+
+    #[test]
+    fn a_successful_disarm_drops_the_defenders_weapon() {
+        let mut attacker = Character::new(Class::Fighter);
+        let mut defender = Character::new(Class::Commoner);
+        defender.equip(Weapon::longsword());
+
+        let command = attacker.maneuver(&defender, Maneuver::Disarm, 15, 10);
+        let result = resolve_maneuver(&command, &mut attacker, &mut defender);
+
+        assert_eq!(Some(ManeuverEffect::Disarmed), result.effect);
+        assert_eq!(None, defender.main_hand);
+        assert_eq!(Some(Weapon::longsword()), defender.dropped_weapon);
+
+        assert!(defender.recover_weapon());
+        assert_eq!(Some(Weapon::longsword()), defender.main_hand);
+        assert!(!defender.conditions.has(Condition::Disarmed));
+        assert!(!defender.recover_weapon());
+    }
+
+    #[test]
+    fn an_unarmed_defender_cannot_be_disarmed() {
+        let mut attacker = Character::new(Class::Fighter);
+        let mut defender = Character::new(Class::Monk);
+
+        let command = attacker.maneuver(&defender, Maneuver::Disarm, 15, 10);
+        let result = resolve_maneuver(&command, &mut attacker, &mut defender);
+
+        assert_eq!(None, result.effect);
+        assert!(!defender.conditions.has(Condition::Disarmed));
+        assert_eq!(0, defender.attack(&attacker, 10).condition_attack_modifier);
+    }
+
+    #[test]
+    fn nimble_defenders_resist_with_dexterity() {
+        let attacker = Character::new(Class::Commoner);
+        let mut defender = Character::new(Class::Rogue);
+        defender.dexterity = 18;
+
+        let command = attacker.maneuver(&defender, Maneuver::Trip, 13, 10);
+
+        assert_eq!(4, command.defender_check.modifier);
+        assert!(!command.succeeds());
+    }
+
+    #[test]
+    fn a_grapple_restricts_both_combatants() {
+        let mut attacker = Character::new(Class::Fighter);
+        attacker.strength = 16;
+        let mut defender = Character::new(Class::Commoner);
+
+        let command = attacker.maneuver(&defender, Maneuver::Grapple, 10, 10);
+        resolve_maneuver(&command, &mut attacker, &mut defender);
+
+        assert!(attacker.conditions.has(Condition::Grappled));
+        assert!(defender.conditions.has(Condition::Grappled));
+    }
+
+    #[test]
+    fn a_shove_pushes_further_the_more_it_wins_by() {
+        let mut attacker = Character::new(Class::Fighter);
+        let mut defender = Character::new(Class::Commoner);
+
+        let command = attacker.maneuver(&defender, Maneuver::Shove, 19, 5);
+        let result = resolve_maneuver(&command, &mut attacker, &mut defender);
+
+        assert_eq!(Some(ManeuverEffect::Shoved { distance: 20 }), result.effect);
+    }
+}
@@ -0,0 +1,263 @@
+use std::cmp;
+use std::collections::BTreeMap;
+
+use character::Character;
+use currency::{CoinPurse, CurrencyError};
+use inventory::{Inventory, InventoryError, Item};
+
+// Every point of charisma modifier shifts a price by 5% in the haggler's
+// favour.
+const HAGGLE_PERCENT_PER_POINT: i64 = 5;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TradeError {
+    NotPriced { id: String },
+    Inventory(InventoryError),
+    Currency(CurrencyError),
+    MerchantCannotAfford { price: u64, available: u64 },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Merchant {
+    pub name: String,
+    pub stock: Inventory,
+    // Base prices in copper pieces, keyed by item id.
+    pub prices: BTreeMap<String, u64>,
+    // Percentages of the base price the merchant sells at and buys at.
+    pub markup: u64,
+    pub buyback: u64,
+    pub purse: CoinPurse,
+}
+
+impl Merchant {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            stock: Inventory::default(),
+            prices: BTreeMap::new(),
+            markup: 120,
+            buyback: 50,
+            purse: CoinPurse::default(),
+        }
+    }
+
+    pub fn with_price(mut self, id: &str, price: u64) -> Self {
+        self.prices.insert(id.to_string(), price);
+        self
+    }
+
+    pub fn with_stock(mut self, item: Item, quantity: u32) -> Self {
+        self.stock.add(item, quantity);
+        self
+    }
+
+    pub fn with_margins(mut self, markup: u64, buyback: u64) -> Self {
+        self.markup = markup;
+        self.buyback = buyback;
+        self
+    }
+
+    fn base_price(&self, id: &str, quantity: u32) -> Result<u64, TradeError> {
+        self.prices
+            .get(id)
+            .map(|price| price * u64::from(quantity))
+            .ok_or_else(|| TradeError::NotPriced { id: id.to_string() })
+    }
+
+    // A charming buyer talks the markup down, but never below the base price.
+    pub fn selling_price(
+        &self,
+        id: &str,
+        quantity: u32,
+        charisma_modifier: i32,
+    ) -> Result<u64, TradeError> {
+        let base_price = self.base_price(id, quantity)?;
+        let haggled = HAGGLE_PERCENT_PER_POINT * i64::from(charisma_modifier);
+        let percent = cmp::max(self.markup as i64 - haggled, 100) as u64;
+        Ok((base_price * percent).div_ceil(100))
+    }
+
+    // A charming seller talks the offer up, but never above the base price.
+    pub fn buying_price(
+        &self,
+        id: &str,
+        quantity: u32,
+        charisma_modifier: i32,
+    ) -> Result<u64, TradeError> {
+        let base_price = self.base_price(id, quantity)?;
+        let haggled = HAGGLE_PERCENT_PER_POINT * i64::from(charisma_modifier);
+        let percent = (self.buyback as i64 + haggled).clamp(0, 100) as u64;
+        Ok(base_price * percent / 100)
+    }
+}
+
+impl Character {
+    pub fn buy(
+        &mut self,
+        merchant: &mut Merchant,
+        id: &str,
+        quantity: u32,
+    ) -> Result<u64, TradeError> {
+        let price = merchant.selling_price(id, quantity, self.charisma_modifier())?;
+        let available = self.purse.total_copper();
+        if price > available {
+            return Err(TradeError::Currency(CurrencyError::InsufficientFunds {
+                cost: price,
+                available,
+            }));
+        }
+
+        let stack = merchant.stock.remove(id, quantity).map_err(TradeError::Inventory)?;
+        self.purse.spend(price).map_err(TradeError::Currency)?;
+        merchant.purse.add_copper(price);
+        self.inventory.add(stack.item, stack.quantity);
+        Ok(price)
+    }
+
+    pub fn sell(
+        &mut self,
+        merchant: &mut Merchant,
+        id: &str,
+        quantity: u32,
+    ) -> Result<u64, TradeError> {
+        let price = merchant.buying_price(id, quantity, self.charisma_modifier())?;
+        let available = merchant.purse.total_copper();
+        if price > available {
+            return Err(TradeError::MerchantCannotAfford { price, available });
+        }
+
+        let stack = self.inventory.remove(id, quantity).map_err(TradeError::Inventory)?;
+        merchant.purse.spend(price).map_err(TradeError::Currency)?;
+        self.purse.add_copper(price);
+        merchant.stock.add(stack.item, stack.quantity);
+        Ok(price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Class;
+    use currency::Denomination;
+    use magic_items::MagicItem;
+    use weapons::Weapon;
+
+    fn blacksmith() -> Merchant {
+        let mut merchant = Merchant::new("blacksmith")
+            .with_price("longsword", 1500)
+            .with_price("dagger", 200)
+            .with_stock(Item::Weapon(Weapon::longsword()), 2)
+            .with_margins(120, 50);
+        merchant.purse.add(Denomination::Gold, 10);
+        merchant
+    }
+
+    fn customer(charisma: u32) -> Character {
+        let mut character = Character::new(Class::Fighter);
+        character.charisma = charisma;
+        character.purse.add(Denomination::Gold, 20);
+        character
+    }
+
+    #[test]
+    fn buying_moves_the_item_and_the_coins() {
+        let mut merchant = blacksmith();
+        let mut fighter = customer(10);
+
+        assert_eq!(Ok(1800), fighter.buy(&mut merchant, "longsword", 1));
+
+        assert_eq!(1, fighter.inventory.quantity("longsword"));
+        assert_eq!(1, merchant.stock.quantity("longsword"));
+        assert_eq!(200, fighter.wealth());
+        assert_eq!(2800, merchant.purse.total_copper());
+    }
+
+    #[test]
+    fn charisma_haggles_the_price_down_but_not_below_cost() {
+        let merchant = blacksmith();
+
+        assert_eq!(Ok(1800), merchant.selling_price("longsword", 1, 0));
+        assert_eq!(Ok(1650), merchant.selling_price("longsword", 1, 2));
+        assert_eq!(Ok(1500), merchant.selling_price("longsword", 1, 5));
+        assert_eq!(Ok(1950), merchant.selling_price("longsword", 1, -2));
+    }
+
+    #[test]
+    fn selling_fetches_a_fraction_of_the_price() {
+        let mut merchant = blacksmith();
+        let mut fighter = customer(14);
+        fighter.inventory.add(Item::Weapon(Weapon::dagger()), 1);
+
+        assert_eq!(Ok(120), fighter.sell(&mut merchant, "dagger", 1));
+
+        assert!(fighter.inventory.is_empty());
+        assert_eq!(1, merchant.stock.quantity("dagger"));
+        assert_eq!(2120, fighter.wealth());
+    }
+
+    #[test]
+    fn same_named_items_are_priced_by_id() {
+        let mut merchant = Merchant::new("jeweller")
+            .with_price("ring-of-protection-1", 2_000)
+            .with_price("ring-of-protection-3", 18_000)
+            .with_stock(
+                Item::MagicItem(MagicItem::ring_of_protection(1).with_id("ring-of-protection-1")),
+                1,
+            )
+            .with_stock(
+                Item::MagicItem(MagicItem::ring_of_protection(3).with_id("ring-of-protection-3")),
+                1,
+            );
+        let mut fighter = customer(10);
+        fighter.purse.add(Denomination::Platinum, 30);
+
+        assert_eq!(Ok(2_400), fighter.buy(&mut merchant, "ring-of-protection-1", 1));
+        assert_eq!(Ok(21_600), fighter.buy(&mut merchant, "ring-of-protection-3", 1));
+        assert_eq!(1, fighter.inventory.quantity("ring-of-protection-3"));
+    }
+
+    #[test]
+    fn a_trade_fails_without_the_money() {
+        let mut merchant = blacksmith();
+        let mut fighter = customer(10);
+
+        assert_eq!(
+            Err(TradeError::Currency(CurrencyError::InsufficientFunds {
+                cost: 3600,
+                available: 2000,
+            })),
+            fighter.buy(&mut merchant, "longsword", 2)
+        );
+        assert_eq!(2, merchant.stock.quantity("longsword"));
+
+        merchant.purse = CoinPurse::default();
+        fighter.inventory.add(Item::Weapon(Weapon::dagger()), 1);
+        assert_eq!(
+            Err(TradeError::MerchantCannotAfford {
+                price: 100,
+                available: 0,
+            }),
+            fighter.sell(&mut merchant, "dagger", 1)
+        );
+        assert_eq!(1, fighter.inventory.quantity("dagger"));
+    }
+
+    #[test]
+    fn merchants_only_deal_in_what_they_price() {
+        let mut merchant = blacksmith();
+        let mut fighter = customer(10);
+
+        assert_eq!(
+            Err(TradeError::NotPriced {
+                id: "plate".to_string(),
+            }),
+            fighter.buy(&mut merchant, "plate", 1)
+        );
+        assert_eq!(
+            Err(TradeError::Inventory(InventoryError::NotCarried {
+                id: "dagger".to_string(),
+            })),
+            fighter.buy(&mut merchant, "dagger", 1)
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use character::{Character, Class, Race};
+use combat::DiceRollModifier;
+use dice::Dice;
+use weapons::{Weapon, WeaponCategory};
+
+const NON_PROFICIENCY_PENALTY: DiceRollModifier = -4;
+
+const ROGUE_WEAPONS: &[&str] = &["hand-crossbow", "rapier", "sap", "shortbow", "short-sword"];
+
+const MONK_WEAPONS: &[&str] = &["kama", "nunchaku", "sai", "siangham", "shuriken"];
+
+const ELF_WEAPONS: &[&str] = &["longsword", "rapier", "longbow", "shortbow"];
+
+const DWARF_WEAPONS: &[&str] = &["waraxe", "warhammer"];
+
+impl Class {
+    pub fn is_proficient_with(&self, weapon: &Weapon) -> bool {
+        let kind = weapon.kind.as_str();
+        let simple = weapon.category == WeaponCategory::Simple;
+        match *self {
+            Class::Fighter | Class::Paladin => weapon.category != WeaponCategory::Exotic,
+            Class::Rogue => simple || ROGUE_WEAPONS.contains(&kind),
+            Class::Monk => simple || MONK_WEAPONS.contains(&kind),
+            Class::Commoner => simple,
+        }
+    }
+}
+
+impl Race {
+    // Weapons every member of the race grows up with, whatever their class.
+    pub fn is_familiar_with(&self, weapon: &Weapon) -> bool {
+        let kind = weapon.kind.as_str();
+        match *self {
+            Race::Elf => ELF_WEAPONS.contains(&kind),
+            Race::Dwarf => DWARF_WEAPONS.contains(&kind),
+            _ => false,
+        }
+    }
+}
+
+// The dice a monk's unarmed strike would deal at the given level.
+fn monk_unarmed_damage(level: u64) -> Dice {
+    match level {
+        0..=3 => Dice::new(1, 6),
+        4..=7 => Dice::new(1, 8),
+        8..=11 => Dice::new(1, 10),
+        12..=15 => Dice::new(2, 6),
+        16..=19 => Dice::new(2, 8),
+        _ => Dice::new(2, 10),
+    }
+}
+
+impl Character {
+    pub fn is_proficient_with(&self, weapon: &Weapon) -> bool {
+        self.class.is_proficient_with(weapon) || self.race.is_familiar_with(weapon)
+    }
+
+    pub fn proficiency_penalty(&self, weapon: Option<&Weapon>) -> DiceRollModifier {
+        match weapon {
+            Some(weapon) if !self.is_proficient_with(weapon) => NON_PROFICIENCY_PENALTY,
+            _ => 0,
+        }
+    }
+
+    // A monk's special weapons follow the monk's unarmed damage progression
+    // when it beats the weapon's own dice.
+    pub fn weapon_damage_dice(&self, weapon: &Weapon) -> Dice {
+        if self.class == Class::Monk && MONK_WEAPONS.contains(&weapon.kind.as_str()) {
+            let unarmed_damage = monk_unarmed_damage(self.level());
+            if unarmed_damage.average() > weapon.damage_dice.average() {
+                return unarmed_damage;
+            }
+        }
+        weapon.damage_dice
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use damage::DamageType;
+
+    #[test]
+    fn commoners_fumble_with_martial_weapons() {
+        let mut commoner = Character::new(Class::Commoner);
+        commoner.equip(Weapon::greatsword());
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = commoner.attack(&attackee, 12);
+        assert_eq!(-4, attack_command.proficiency_penalty);
+        assert!(!attack_command.succeeds());
+        assert_eq!(
+            "12 roll -4 not proficient = 8 vs AC 10 = 10: miss",
+            attack_command.explain().to_string()
+        );
+    }
+
+    #[test]
+    fn fighters_are_proficient_with_martial_weapons() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.equip(Weapon::greatsword());
+        let attackee = Character::new(Class::Commoner);
+
+        assert_eq!(0, fighter.attack(&attackee, 12).proficiency_penalty);
+    }
+
+    #[test]
+    fn unarmed_attacks_need_no_proficiency() {
+        let commoner = Character::new(Class::Commoner);
+
+        assert_eq!(0, commoner.proficiency_penalty(None));
+    }
+
+    #[test]
+    fn races_are_familiar_with_their_traditional_weapons() {
+        let mut elf = Character::new(Class::Commoner);
+        elf.race = Race::Elf;
+        let mut dwarf = Character::new(Class::Commoner);
+        dwarf.race = Race::Dwarf;
+
+        assert!(elf.is_proficient_with(&Weapon::longbow()));
+        assert!(!elf.is_proficient_with(&Weapon::waraxe()));
+        assert!(dwarf.is_proficient_with(&Weapon::waraxe()));
+    }
+
+    #[test]
+    fn proficiency_goes_by_the_kind_of_weapon_not_its_name() {
+        let mut elf = Character::new(Class::Commoner);
+        elf.race = Race::Elf;
+        let longsword = Weapon::new("Longsword", Dice::new(1, 8), DamageType::Slashing)
+            .with_category(WeaponCategory::Martial);
+        let plus_one_longsword = Weapon::new("+1 longsword", Dice::new(1, 8), DamageType::Slashing)
+            .with_kind("longsword")
+            .with_category(WeaponCategory::Martial)
+            .with_magic_bonus(1);
+
+        assert!(elf.is_proficient_with(&longsword));
+        assert!(elf.is_proficient_with(&plus_one_longsword));
+        assert!(!Character::new(Class::Commoner).is_proficient_with(&plus_one_longsword));
+    }
+
+    #[test]
+    fn only_monks_are_trained_with_nunchaku() {
+        assert!(Class::Monk.is_proficient_with(&Weapon::nunchaku()));
+        assert!(!Class::Fighter.is_proficient_with(&Weapon::nunchaku()));
+    }
+
+    #[test]
+    fn a_monks_nunchaku_follows_the_unarmed_progression() {
+        let mut monk = Character::new(Class::Monk);
+        monk.equip(Weapon::nunchaku());
+        let attackee = Character::new(Class::Commoner);
+
+        assert_eq!(Some(Dice::new(1, 6)), monk.attack(&attackee, 15).damage_dice);
+
+        monk.experience_points = 7000;
+        assert_eq!(Some(Dice::new(1, 10)), monk.attack(&attackee, 15).damage_dice);
+
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.experience_points = 7000;
+        fighter.equip(Weapon::nunchaku());
+        assert_eq!(Some(Dice::new(1, 6)), fighter.attack(&attackee, 15).damage_dice);
+    }
+}
@@ -0,0 +1,190 @@
+use advantage::AdvantageSource;
+use character::{Ability, Character};
+use combat::{AttackCommand, DiceRollModifier};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Cover {
+    #[default]
+    None,
+    Half,
+    ThreeQuarters,
+}
+
+impl Cover {
+    pub fn armor_class_bonus(&self) -> DiceRollModifier {
+        match *self {
+            Cover::None => 0,
+            Cover::Half => 2,
+            Cover::ThreeQuarters => 5,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RangedAttack {
+    distance: u32,
+    range_increment: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RangedAttackError {
+    OutOfRange { distance: u32, maximum_range: u32 },
+    ZeroRangeIncrement,
+}
+
+impl RangedAttack {
+    pub fn new(distance: u32, range_increment: u32) -> Result<Self, RangedAttackError> {
+        if range_increment == 0 {
+            return Err(RangedAttackError::ZeroRangeIncrement);
+        }
+        Ok(Self {
+            distance,
+            range_increment,
+        })
+    }
+
+    pub fn distance(&self) -> u32 {
+        self.distance
+    }
+
+    pub fn range_increment(&self) -> u32 {
+        self.range_increment
+    }
+
+    pub fn maximum_range(&self) -> u32 {
+        self.range_increment * 10
+    }
+
+    // -2 for every full range increment between the attacker and the target.
+    pub fn range_penalty(&self) -> DiceRollModifier {
+        let increments = self.distance.saturating_sub(1) / self.range_increment;
+        -2 * increments as DiceRollModifier
+    }
+}
+
+impl Character {
+    pub fn ranged_attack(
+        &self,
+        attackee: &Character,
+        dice_roll: u32,
+        ranged_attack: RangedAttack,
+    ) -> Result<AttackCommand, RangedAttackError> {
+        if ranged_attack.distance > ranged_attack.maximum_range() {
+            return Err(RangedAttackError::OutOfRange {
+                distance: ranged_attack.distance,
+                maximum_range: ranged_attack.maximum_range(),
+            });
+        }
+
+        Ok(AttackCommand {
+            attack_ability: Ability::Dexterity,
+            attack_modifier: self.dexterity_modifier(),
+            damage_modifier: self.dexterity_modifier(),
+            range_penalty: ranged_attack.range_penalty(),
+            ..self.attack(attackee, dice_roll)
+        })
+    }
+
+    pub fn ranged_attack_with_rolls(
+        &self,
+        attackee: &Character,
+        dice_rolls: (u32, u32),
+        ranged_attack: RangedAttack,
+        sources: &[AdvantageSource],
+    ) -> Result<AttackCommand, RangedAttackError> {
+        let roll_mode = self.roll_mode_against(attackee, sources);
+        self.ranged_attack(attackee, dice_rolls.0, ranged_attack)
+            .map(|command| command.with_rolls(dice_rolls, roll_mode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advantage::RollMode;
+    use character::Class;
+
+    #[test]
+    fn a_hidden_archer_shoots_with_advantage() {
+        let archer = Character::new(Class::Fighter);
+        let target = Character::new(Class::Commoner);
+
+        let attack_command = archer
+            .ranged_attack_with_rolls(
+                &target,
+                (3, 17),
+                RangedAttack::new(30, 100).unwrap(),
+                &[AdvantageSource::HiddenAttacker],
+            )
+            .unwrap();
+        assert_eq!(RollMode::Advantage, attack_command.roll_mode);
+        assert_eq!(17, attack_command.dice_roll);
+        assert_eq!(Ability::Dexterity, attack_command.attack_ability);
+    }
+
+    #[test]
+    fn ranged_attacks_use_dexterity_for_every_class() {
+        let mut archer = Character::new(Class::Fighter);
+        archer.strength = 6;
+        archer.dexterity = 16;
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = archer
+            .ranged_attack(&attackee, 10, RangedAttack::new(30, 60).unwrap())
+            .unwrap();
+        assert_eq!(3, attack_command.attack_modifier);
+        assert_eq!(0, attack_command.range_penalty);
+    }
+
+    #[test]
+    fn every_range_increment_imposes_a_penalty() {
+        assert_eq!(0, RangedAttack::new(60, 60).unwrap().range_penalty());
+        assert_eq!(-2, RangedAttack::new(61, 60).unwrap().range_penalty());
+        assert_eq!(-6, RangedAttack::new(200, 60).unwrap().range_penalty());
+
+        let archer = Character::new(Class::Fighter);
+        let attackee = Character::new(Class::Commoner);
+        let attack_command = archer
+            .ranged_attack(&attackee, 10, RangedAttack::new(130, 60).unwrap())
+            .unwrap();
+        assert!(!attack_command.succeeds());
+    }
+
+    #[test]
+    fn targets_beyond_maximum_range_cannot_be_attacked() {
+        let archer = Character::new(Class::Fighter);
+        let attackee = Character::new(Class::Commoner);
+
+        assert_eq!(
+            Err(RangedAttackError::OutOfRange {
+                distance: 110,
+                maximum_range: 100,
+            }),
+            archer.ranged_attack(&attackee, 20, RangedAttack::new(110, 10).unwrap())
+        );
+        assert_eq!(
+            Err(RangedAttackError::ZeroRangeIncrement),
+            RangedAttack::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn cover_raises_the_target_number() {
+        let archer = Character::new(Class::Fighter);
+        let mut attackee = Character::new(Class::Commoner);
+        attackee.cover = Cover::Half;
+
+        let attack_command = archer
+            .ranged_attack(&attackee, 11, RangedAttack::new(10, 60).unwrap())
+            .unwrap();
+        assert_eq!(2, attack_command.cover_modifier);
+        assert_eq!(12, attack_command.target_number());
+        assert!(attack_command.succeeds());
+
+        attackee.cover = Cover::ThreeQuarters;
+        let attack_command = archer
+            .ranged_attack(&attackee, 11, RangedAttack::new(10, 60).unwrap())
+            .unwrap();
+        assert!(!attack_command.succeeds());
+    }
+}
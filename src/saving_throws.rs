@@ -0,0 +1,145 @@
+use character::{Character, Class};
+use combat::DiceRollModifier;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SaveKind {
+    Fortitude,
+    Reflex,
+    Will,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SavingThrowResult {
+    pub kind: SaveKind,
+    pub dice_roll: u32,
+    pub base_save_bonus: DiceRollModifier,
+    pub ability_modifier: DiceRollModifier,
+    pub total: i32,
+    pub difficulty_class: i32,
+    pub succeeded: bool,
+}
+
+impl SavingThrowResult {
+    pub fn margin(&self) -> i32 {
+        self.total - self.difficulty_class
+    }
+}
+
+fn good_save(level: u64) -> DiceRollModifier {
+    2 + (level / 2) as DiceRollModifier
+}
+
+fn poor_save(level: u64) -> DiceRollModifier {
+    (level / 3) as DiceRollModifier
+}
+
+impl Character {
+    pub fn base_save_bonus(&self, kind: SaveKind) -> DiceRollModifier {
+        let good = matches!(
+            (self.class, kind),
+            (Class::Monk, _) |
+                (Class::Fighter, SaveKind::Fortitude) |
+                (Class::Paladin, SaveKind::Fortitude) |
+                (Class::Rogue, SaveKind::Reflex)
+        );
+
+        if good {
+            good_save(self.level())
+        } else {
+            poor_save(self.level())
+        }
+    }
+
+    pub fn save_ability_modifier(&self, kind: SaveKind) -> DiceRollModifier {
+        match kind {
+            SaveKind::Fortitude => self.constitution_modifier(),
+            SaveKind::Reflex => self.dexterity_modifier(),
+            SaveKind::Will => self.wisdom_modifier(),
+        }
+    }
+
+    // A natural 20 always saves and a natural 1 always fails.
+    pub fn saving_throw(
+        &self,
+        kind: SaveKind,
+        difficulty_class: i32,
+        dice_roll: u32,
+    ) -> SavingThrowResult {
+        let base_save_bonus = self.base_save_bonus(kind);
+        let ability_modifier = self.save_ability_modifier(kind);
+        let total = dice_roll as i32 + base_save_bonus + ability_modifier;
+
+        SavingThrowResult {
+            kind,
+            dice_roll,
+            base_save_bonus,
+            ability_modifier,
+            total,
+            difficulty_class,
+            succeeded: match dice_roll {
+                1 => false,
+                20 => true,
+                _ => total >= difficulty_class,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saves_use_their_ability_modifier() {
+        let mut character = Character::new(Class::Commoner);
+        character.constitution = 14;
+        character.dexterity = 8;
+        character.wisdom = 16;
+
+        let fortitude = character.saving_throw(SaveKind::Fortitude, 15, 10);
+        let reflex = character.saving_throw(SaveKind::Reflex, 15, 10);
+        let will = character.saving_throw(SaveKind::Will, 15, 10);
+
+        assert_eq!(2, fortitude.ability_modifier);
+        assert_eq!(-1, reflex.ability_modifier);
+        assert_eq!(3, will.ability_modifier);
+    }
+
+    #[test]
+    fn classes_have_good_and_poor_save_progressions() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.experience_points = 5000;
+
+        assert_eq!(5, fighter.base_save_bonus(SaveKind::Fortitude));
+        assert_eq!(2, fighter.base_save_bonus(SaveKind::Reflex));
+
+        let mut rogue = Character::new(Class::Rogue);
+        rogue.experience_points = 5000;
+        assert_eq!(5, rogue.base_save_bonus(SaveKind::Reflex));
+        assert_eq!(2, rogue.base_save_bonus(SaveKind::Will));
+
+        let monk = Character::new(Class::Monk);
+        assert_eq!(2, monk.base_save_bonus(SaveKind::Will));
+    }
+
+    #[test]
+    fn a_save_succeeds_when_meeting_the_difficulty_class() {
+        let fighter = Character::new(Class::Fighter);
+
+        let result = fighter.saving_throw(SaveKind::Fortitude, 14, 12);
+        assert_eq!(14, result.total);
+        assert_eq!(0, result.margin());
+        assert!(result.succeeded);
+
+        assert!(!fighter.saving_throw(SaveKind::Reflex, 14, 12).succeeded);
+    }
+
+    #[test]
+    fn natural_rolls_override_the_difficulty_class() {
+        let mut monk = Character::new(Class::Monk);
+        monk.wisdom = 20;
+
+        assert!(!monk.saving_throw(SaveKind::Will, 2, 1).succeeded);
+        assert!(monk.saving_throw(SaveKind::Will, 40, 20).succeeded);
+    }
+}
@@ -0,0 +1,195 @@
+use character::{Character, Class};
+use combat::DiceRollModifier;
+use inventory::{enhanced_id, item_id};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Shield {
+    pub id: String,
+    pub name: String,
+    pub armor_bonus: DiceRollModifier,
+    pub magic_bonus: DiceRollModifier,
+    pub attack_penalty: DiceRollModifier,
+    pub weight: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ShieldError {
+    OffHandOccupied { shield: String, off_hand: String },
+}
+
+impl Shield {
+    pub fn new(
+        name: &str,
+        armor_bonus: DiceRollModifier,
+        attack_penalty: DiceRollModifier,
+    ) -> Self {
+        Self {
+            id: item_id(name),
+            name: name.to_string(),
+            armor_bonus,
+            magic_bonus: 0,
+            attack_penalty,
+            weight: 0,
+        }
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_magic_bonus(mut self, magic_bonus: DiceRollModifier) -> Self {
+        self.id = enhanced_id(&self.id, magic_bonus);
+        self.magic_bonus = magic_bonus;
+        self
+    }
+
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn buckler() -> Self {
+        Shield::new("buckler", 1, -1).with_weight(5)
+    }
+
+    pub fn heavy_shield() -> Self {
+        Shield::new("heavy shield", 2, -2).with_weight(15)
+    }
+
+    pub fn tower_shield() -> Self {
+        Shield::new("tower shield", 4, -4).with_weight(45)
+    }
+}
+
+impl Character {
+    // A shield needs the off hand, so any weapon held there has to be put
+    // away first.
+    pub(crate) fn check_off_hand_free(&self, shield: &Shield) -> Result<(), ShieldError> {
+        match self.off_hand {
+            Some(ref weapon) => Err(ShieldError::OffHandOccupied {
+                shield: shield.name.clone(),
+                off_hand: weapon.name.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn equip_shield(&mut self, shield: Shield) -> Result<Option<Shield>, ShieldError> {
+        self.check_off_hand_free(&shield)?;
+        Ok(self.shield.replace(shield))
+    }
+
+    pub fn unequip_shield(&mut self) -> Option<Shield> {
+        self.shield.take()
+    }
+
+    pub fn shield_bonus(&self) -> DiceRollModifier {
+        self.shield
+            .as_ref()
+            .map_or(0, |shield| shield.armor_bonus + shield.magic_bonus)
+    }
+
+    // Fighters are trained with shields and take only half the penalty,
+    // rounded in their favour.
+    pub fn shield_attack_penalty(&self) -> DiceRollModifier {
+        let attack_penalty = self.shield.as_ref().map_or(0, |shield| shield.attack_penalty);
+        match self.class {
+            Class::Fighter => attack_penalty / 2,
+            _ => attack_penalty,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use two_weapon::TwoWeaponFighting;
+    use weapons::Weapon;
+
+    #[test]
+    fn a_shield_adds_to_armor_class() {
+        let mut character = Character::new(Class::Commoner);
+
+        assert_eq!(Ok(None), character.equip_shield(Shield::heavy_shield()));
+        assert_eq!(12, character.armor_class());
+        assert_eq!(Some(Shield::heavy_shield()), character.unequip_shield());
+        assert_eq!(10, character.armor_class());
+    }
+
+    #[test]
+    fn a_shield_penalises_the_wielders_attacks() {
+        let mut rogue = Character::new(Class::Rogue);
+        rogue.equip_shield(Shield::tower_shield()).unwrap();
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = rogue.attack(&attackee, 13);
+        assert_eq!(-4, attack_command.shield_penalty);
+        assert!(!attack_command.succeeds());
+    }
+
+    #[test]
+    fn fighters_take_half_the_shield_penalty() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.equip_shield(Shield::tower_shield()).unwrap();
+        assert_eq!(-2, fighter.shield_attack_penalty());
+
+        fighter.equip_shield(Shield::buckler()).unwrap();
+        assert_eq!(0, fighter.shield_attack_penalty());
+    }
+
+    #[test]
+    fn a_shield_rules_out_two_weapon_fighting() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.two_weapon_fighting = Some(TwoWeaponFighting::default());
+        fighter.equip_shield(Shield::buckler()).unwrap();
+        // Set directly, since equipping refuses a weapon next to a shield.
+        fighter.off_hand = Some(Weapon::dagger());
+        let attackee = Character::new(Class::Commoner);
+
+        assert_eq!(0, fighter.attack(&attackee, 10).two_weapon_penalty);
+        assert_eq!(None, fighter.off_hand_attack(&attackee, 20));
+    }
+
+    #[test]
+    fn a_shield_cannot_be_taken_up_with_a_weapon_in_the_off_hand() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.equip_off_hand(Weapon::dagger()).unwrap();
+
+        assert_eq!(
+            Err(ShieldError::OffHandOccupied {
+                shield: "buckler".to_string(),
+                off_hand: "dagger".to_string(),
+            }),
+            fighter.equip_shield(Shield::buckler())
+        );
+        assert_eq!(None, fighter.shield);
+    }
+
+    #[test]
+    fn a_weapon_cannot_be_taken_up_in_the_hand_holding_a_shield() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.equip_shield(Shield::buckler()).unwrap();
+
+        assert_eq!(
+            Err(ShieldError::OffHandOccupied {
+                shield: "buckler".to_string(),
+                off_hand: "dagger".to_string(),
+            }),
+            fighter.equip_off_hand(Weapon::dagger())
+        );
+        assert_eq!(None, fighter.off_hand);
+    }
+
+    #[test]
+    fn the_shield_penalty_is_called_out_in_the_breakdown() {
+        let mut rogue = Character::new(Class::Rogue);
+        rogue.equip_shield(Shield::heavy_shield()).unwrap();
+        let attackee = Character::new(Class::Commoner);
+
+        assert_eq!(
+            "14 roll -2 shield = 12 vs AC 10 = 10: hit\n1 base = 1 damage",
+            rogue.attack(&attackee, 14).explain().to_string()
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use rand::{SeedableRng, StdRng};
+
+use catalog::ItemCatalog;
+use character::Character;
+use dice::{Dice, DiceRoller};
+use encounter::{Encounter, Side};
+use inventory::Item;
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Treasure {
+    pub copper_pieces: u64,
+    // Catalog ids, so the treasure can be stored and replayed.
+    pub item_ids: Vec<String>,
+}
+
+// The strongest single bonus an item grants; enhanced weapons, armor and
+// shields count as magic too.
+fn magic_item_power(item: &Item) -> Option<i32> {
+    let power = match *item {
+        Item::MagicItem(ref magic_item) => {
+            return magic_item.bonuses.iter().map(|bonus| bonus.value).max()
+        }
+        Item::Weapon(ref weapon) => weapon.magic_bonus,
+        Item::Armor(ref armor) => armor.magic_bonus,
+        Item::Shield(ref shield) => shield.magic_bonus,
+        Item::Consumable(_) | Item::Gear { .. } => return None,
+    };
+    Some(power).filter(|&power| power > 0)
+}
+
+fn is_mundane(item: &Item) -> bool {
+    matches!(*item, Item::Weapon(_) | Item::Armor(_) | Item::Shield(_)) &&
+        magic_item_power(item).is_none()
+}
+
+// Every generator built from the same seed hands out the same treasure, so
+// sessions can be replayed.
+pub struct TreasureGenerator {
+    rng: StdRng,
+}
+
+impl TreasureGenerator {
+    pub fn new(seed: usize) -> Self {
+        Self {
+            rng: StdRng::from_seed(&[seed][..]),
+        }
+    }
+
+    fn choose(&mut self, ids: &[&String]) -> Option<String> {
+        if ids.is_empty() {
+            return None;
+        }
+        let index = self.rng.roll(ids.len() as u32) - 1;
+        Some(ids[index as usize].clone())
+    }
+
+    // Each defeated opponent carries its level in d6 gold pieces, has a one in
+    // four chance of dropping a mundane catalog item, and a 5% per level
+    // chance of dropping a magic item no stronger than +1 per five levels.
+    pub fn generate(&mut self, defeated: &[&Character], catalog: &ItemCatalog) -> Treasure {
+        let mundane: Vec<&String> = catalog
+            .iter()
+            .filter(|&(_, item)| is_mundane(item))
+            .map(|(id, _)| id)
+            .collect();
+
+        let mut treasure = Treasure::default();
+
+        for opponent in defeated {
+            let level = opponent.level();
+
+            let gold_pieces = Dice::new(level as u32, 6).roll(&mut self.rng);
+            treasure.copper_pieces += u64::from(gold_pieces) * 100;
+
+            if self.rng.roll(4) == 4 {
+                treasure.item_ids.extend(self.choose(&mundane));
+            }
+
+            if u64::from(self.rng.roll(100)) <= level * 5 {
+                let strongest = 1 + (level / 5) as i32;
+                let magic: Vec<&String> = catalog
+                    .iter()
+                    .filter(|&(_, item)| {
+                        magic_item_power(item).is_some_and(|power| power <= strongest)
+                    })
+                    .map(|(id, _)| id)
+                    .collect();
+                treasure.item_ids.extend(self.choose(&magic));
+            }
+        }
+
+        treasure
+    }
+}
+
+impl Encounter {
+    // Only a finished encounter has loot, taken from the losing side.
+    pub fn treasure(
+        &self,
+        generator: &mut TreasureGenerator,
+        catalog: &ItemCatalog,
+    ) -> Option<Treasure> {
+        let losers = match self.winner()? {
+            Side::Heroes => Side::Monsters,
+            Side::Monsters => Side::Heroes,
+        };
+        Some(generator.generate(&self.defeated(losers), catalog))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Class;
+
+    fn catalog() -> ItemCatalog {
+        ItemCatalog::from_toml(
+            r#"
+            [[weapons]]
+            id = "dagger"
+            name = "dagger"
+            damage = "1d4"
+            damage_type = "piercing"
+
+            [[shields]]
+            id = "buckler"
+            name = "buckler"
+            armor_bonus = 1
+            attack_penalty = -1
+
+            [[magic_items]]
+            id = "ring-of-protection-1"
+            name = "ring of protection"
+            bonuses = [{ target = "armor_class", value = 1 }]
+
+            [[magic_items]]
+            id = "ring-of-protection-3"
+            name = "ring of protection"
+            bonuses = [{ target = "armor_class", value = 3 }]
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn opponent(level: u64) -> Character {
+        let mut character = Character::new(Class::Fighter);
+        character.experience_points = (level - 1) * 1000;
+        character
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_treasure() {
+        let catalog = catalog();
+        let ogre = opponent(8);
+        let goblin = opponent(1);
+
+        let first = TreasureGenerator::new(42).generate(&[&ogre, &goblin], &catalog);
+        let second = TreasureGenerator::new(42).generate(&[&ogre, &goblin], &catalog);
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn a_seeded_treasure_snapshot() {
+        let catalog = catalog();
+        let opponents: Vec<Character> = vec![opponent(3), opponent(9), opponent(15)];
+        let defeated: Vec<&Character> = opponents.iter().collect();
+
+        let treasure = TreasureGenerator::new(7).generate(&defeated, &catalog);
+
+        assert_eq!(9_800, treasure.copper_pieces);
+        assert_eq!(vec!["ring-of-protection-1", "ring-of-protection-1"], treasure.item_ids);
+    }
+
+    #[test]
+    fn coins_scale_with_the_opponents_level() {
+        let catalog = ItemCatalog::default();
+        let dragon = opponent(20);
+
+        let treasure = TreasureGenerator::new(1).generate(&[&dragon], &catalog);
+
+        assert!((2_000..=12_000).contains(&treasure.copper_pieces));
+        assert!(treasure.item_ids.is_empty());
+    }
+
+    #[test]
+    fn weak_opponents_never_drop_strong_magic() {
+        let catalog = catalog();
+        let goblins: Vec<Character> = (0..200).map(|_| opponent(4)).collect();
+        let defeated: Vec<&Character> = goblins.iter().collect();
+
+        let treasure = TreasureGenerator::new(3).generate(&defeated, &catalog);
+
+        assert!(treasure.item_ids.iter().any(|id| id == "ring-of-protection-1"));
+        assert!(!treasure.item_ids.iter().any(|id| id == "ring-of-protection-3"));
+    }
+
+    #[test]
+    fn enhanced_weapons_are_magic_treasure() {
+        let catalog = ItemCatalog::from_toml(
+            r#"
+            [[weapons]]
+            id = "longsword-plus-3"
+            name = "longsword"
+            damage = "1d8"
+            damage_type = "slashing"
+            magic_bonus = 3
+            "#,
+        )
+        .unwrap();
+        let goblins: Vec<Character> = (0..200).map(|_| opponent(1)).collect();
+        let defeated: Vec<&Character> = goblins.iter().collect();
+
+        let treasure = TreasureGenerator::new(3).generate(&defeated, &catalog);
+        assert!(treasure.item_ids.is_empty());
+
+        let dragon = opponent(10);
+        let dragons: Vec<&Character> = (0..50).map(|_| &dragon).collect();
+        let treasure = TreasureGenerator::new(3).generate(&dragons, &catalog);
+        assert!(treasure.item_ids.iter().any(|id| id == "longsword-plus-3"));
+    }
+
+    #[test]
+    fn an_encounter_hands_out_the_losers_treasure() {
+        let catalog = catalog();
+        let mut encounter = Encounter::new();
+        encounter.add(Character::new(Class::Fighter), Side::Heroes);
+        let goblin = encounter.add(opponent(3), Side::Monsters);
+
+        assert_eq!(None, encounter.treasure(&mut TreasureGenerator::new(5), &catalog));
+
+        encounter.combatant_mut(goblin).unwrap().character.damage = 100;
+        let treasure = encounter
+            .treasure(&mut TreasureGenerator::new(5), &catalog)
+            .unwrap();
+        assert!(treasure.copper_pieces >= 300);
+    }
+}
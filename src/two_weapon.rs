@@ -0,0 +1,207 @@
+use std::cmp;
+
+use advantage::AdvantageSource;
+use character::Character;
+use combat::{AttackCommand, DiceRollModifier};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TwoWeaponFighting {
+    pub main_hand_penalty: DiceRollModifier,
+    pub off_hand_penalty: DiceRollModifier,
+    pub light_off_hand: bool,
+}
+
+impl Default for TwoWeaponFighting {
+    fn default() -> Self {
+        Self {
+            main_hand_penalty: -6,
+            off_hand_penalty: -10,
+            light_off_hand: false,
+        }
+    }
+}
+
+impl TwoWeaponFighting {
+    // A light weapon in the off hand eases both penalties by 2.
+    fn light_weapon_reduction(&self) -> DiceRollModifier {
+        if self.light_off_hand {
+            2
+        } else {
+            0
+        }
+    }
+
+    pub fn main_hand_attack_penalty(&self) -> DiceRollModifier {
+        cmp::min(self.main_hand_penalty + self.light_weapon_reduction(), 0)
+    }
+
+    pub fn off_hand_attack_penalty(&self) -> DiceRollModifier {
+        cmp::min(self.off_hand_penalty + self.light_weapon_reduction(), 0)
+    }
+}
+
+// The off hand always deals strength damage, even for a character who attacks
+// with dexterity. Only a bonus is halved; a penalty applies in full.
+fn off_hand_damage_modifier(modifier: DiceRollModifier) -> DiceRollModifier {
+    if modifier > 0 {
+        modifier / 2
+    } else {
+        modifier
+    }
+}
+
+impl Character {
+    // Two-weapon fighting needs a weapon in the off hand. A light one counts
+    // even when the configuration does not say so. A shield leaves no hand
+    // free for a second weapon.
+    pub(crate) fn effective_two_weapon_fighting(&self) -> Option<TwoWeaponFighting> {
+        if self.shield.is_some() {
+            return None;
+        }
+        let mut two_weapon_fighting = self.two_weapon_fighting?;
+        two_weapon_fighting.light_off_hand |= self.off_hand.as_ref()?.light;
+        Some(two_weapon_fighting)
+    }
+
+    pub fn off_hand_attack(&self, attackee: &Character, dice_roll: u32) -> Option<AttackCommand> {
+        let two_weapon_fighting = self.effective_two_weapon_fighting()?;
+        let off_hand = self.attack_with_weapon(attackee, dice_roll, self.off_hand.as_ref());
+
+        Some(AttackCommand {
+            off_hand: true,
+            two_weapon_penalty: two_weapon_fighting.off_hand_attack_penalty(),
+            damage_modifier: off_hand_damage_modifier(self.strength_modifier()),
+            ..off_hand
+        })
+    }
+
+    pub fn off_hand_attack_with_rolls(
+        &self,
+        attackee: &Character,
+        dice_rolls: (u32, u32),
+        sources: &[AdvantageSource],
+    ) -> Option<AttackCommand> {
+        let roll_mode = self.roll_mode_against(attackee, sources);
+        self.off_hand_attack(attackee, dice_rolls.0)
+            .map(|command| command.with_rolls(dice_rolls, roll_mode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advantage::RollMode;
+    use character::Class;
+    use weapons::Weapon;
+
+    fn two_weapon_fighter(class: Class, off_hand: Weapon) -> Character {
+        let mut character = Character::new(class);
+        character.two_weapon_fighting = Some(TwoWeaponFighting::default());
+        character.equip_off_hand(off_hand).unwrap();
+        character
+    }
+
+    #[test]
+    fn wielding_two_weapons_penalises_the_main_hand() {
+        let attacker = two_weapon_fighter(Class::Fighter, Weapon::longsword());
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = attacker.attack(&attackee, 14);
+        assert_eq!(-6, attack_command.two_weapon_penalty);
+        assert!(!attack_command.succeeds());
+    }
+
+    #[test]
+    fn only_characters_with_two_weapons_get_an_off_hand_attack() {
+        let attacker = Character::new(Class::Fighter);
+        let attackee = Character::new(Class::Commoner);
+
+        assert_eq!(None, attacker.off_hand_attack(&attackee, 20));
+    }
+
+    #[test]
+    fn the_configuration_alone_does_not_make_a_second_weapon() {
+        let mut attacker = Character::new(Class::Fighter);
+        attacker.equip(Weapon::longsword());
+        attacker.two_weapon_fighting = Some(TwoWeaponFighting::default());
+        let attackee = Character::new(Class::Commoner);
+
+        assert_eq!(0, attacker.attack(&attackee, 14).two_weapon_penalty);
+        assert_eq!(None, attacker.off_hand_attack(&attackee, 20));
+    }
+
+    #[test]
+    fn the_off_hand_deals_half_the_strength_bonus() {
+        let mut attacker = two_weapon_fighter(Class::Fighter, Weapon::longsword());
+        attacker.strength = 18;
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = attacker.off_hand_attack(&attackee, 18).unwrap();
+        assert!(attack_command.off_hand);
+        assert_eq!(-10, attack_command.two_weapon_penalty);
+        assert_eq!(2, attack_command.damage_modifier);
+        assert_eq!(Some(6), attack_command.damage());
+    }
+
+    #[test]
+    fn the_off_hand_takes_a_strength_penalty_in_full() {
+        let mut attacker = two_weapon_fighter(Class::Fighter, Weapon::longsword());
+        attacker.strength = 6;
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = attacker.off_hand_attack(&attackee, 20).unwrap();
+        assert_eq!(-2, attack_command.damage_modifier);
+    }
+
+    #[test]
+    fn a_rogue_still_deals_strength_damage_with_the_off_hand() {
+        let mut attacker = two_weapon_fighter(Class::Rogue, Weapon::dagger());
+        attacker.strength = 12;
+        attacker.dexterity = 18;
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = attacker.off_hand_attack(&attackee, 20).unwrap();
+        assert_eq!(4, attack_command.attack_modifier);
+        assert_eq!(0, attack_command.damage_modifier);
+    }
+
+    #[test]
+    fn the_off_hand_can_attack_with_disadvantage() {
+        let attacker = two_weapon_fighter(Class::Fighter, Weapon::dagger());
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = attacker
+            .off_hand_attack_with_rolls(&attackee, (18, 5), &[AdvantageSource::BlindedAttacker])
+            .unwrap();
+        assert!(attack_command.off_hand);
+        assert_eq!(RollMode::Disadvantage, attack_command.roll_mode);
+        assert_eq!(5, attack_command.dice_roll);
+        assert_eq!(vec![18, 5], attack_command.raw_dice_rolls);
+    }
+
+    #[test]
+    fn a_light_off_hand_weapon_eases_both_penalties() {
+        let two_weapon_fighting = TwoWeaponFighting {
+            main_hand_penalty: -4,
+            off_hand_penalty: -4,
+            light_off_hand: true,
+        };
+
+        assert_eq!(-2, two_weapon_fighting.main_hand_attack_penalty());
+        assert_eq!(-2, two_weapon_fighting.off_hand_attack_penalty());
+    }
+
+    #[test]
+    fn the_off_hand_is_called_out_in_the_breakdown() {
+        let mut attacker = two_weapon_fighter(Class::Fighter, Weapon::dagger());
+        attacker.strength = 14;
+        let attackee = Character::new(Class::Commoner);
+
+        let breakdown = attacker.off_hand_attack(&attackee, 20).unwrap().explain();
+        assert_eq!(
+            "20 roll +2 STR +1 level -8 off hand = 15 vs AC 10 = 10: critical hit\n\
+             4 from 1d4 +2 off-hand STR (x2 critical) = 6 damage",
+            breakdown.to_string()
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use character::{Character, Class};
+use combat::DiceRollModifier;
+use conditions::Condition;
+use damage::DamageType;
+use dice::Dice;
+use inventory::{enhanced_id, item_id};
+use shields::ShieldError;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeaponCategory {
+    #[default]
+    Simple,
+    Martial,
+    Exotic,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Weapon {
+    pub id: String,
+    pub name: String,
+    // What sort of weapon this is, whatever it is called; proficiency and
+    // racial familiarity go by the kind.
+    pub kind: String,
+    pub category: WeaponCategory,
+    pub damage_dice: Dice,
+    pub damage_type: DamageType,
+    pub magic_bonus: DiceRollModifier,
+    pub critical_threat_range: u32,
+    pub critical_multiplier: i32,
+    pub class_critical_multipliers: Vec<(Class, i32)>,
+    pub light: bool,
+    pub weight: u32,
+}
+
+impl Weapon {
+    pub fn new(name: &str, damage_dice: Dice, damage_type: DamageType) -> Self {
+        Self {
+            id: item_id(name),
+            name: name.to_string(),
+            kind: item_id(name),
+            category: WeaponCategory::default(),
+            damage_dice,
+            damage_type,
+            magic_bonus: 0,
+            critical_threat_range: 20,
+            critical_multiplier: 2,
+            class_critical_multipliers: Vec::new(),
+            light: false,
+            weight: 0,
+        }
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = id.to_string();
+        self
+    }
+
+    pub fn with_kind(mut self, kind: &str) -> Self {
+        self.kind = kind.to_string();
+        self
+    }
+
+    pub fn with_category(mut self, category: WeaponCategory) -> Self {
+        self.category = category;
+        self
+    }
+
+    pub fn with_magic_bonus(mut self, magic_bonus: DiceRollModifier) -> Self {
+        self.id = enhanced_id(&self.id, magic_bonus);
+        self.magic_bonus = magic_bonus;
+        self
+    }
+
+    pub fn with_critical(mut self, critical_threat_range: u32, critical_multiplier: i32) -> Self {
+        self.critical_threat_range = critical_threat_range;
+        self.critical_multiplier = critical_multiplier;
+        self
+    }
+
+    pub fn with_class_critical_multiplier(
+        mut self,
+        class: Class,
+        critical_multiplier: i32,
+    ) -> Self {
+        self.class_critical_multipliers.push((class, critical_multiplier));
+        self
+    }
+
+    pub fn light(mut self) -> Self {
+        self.light = true;
+        self
+    }
+
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn critical_multiplier_for(&self, class: Class) -> Option<i32> {
+        self.class_critical_multipliers
+            .iter()
+            .find(|&&(weapon_class, _)| weapon_class == class)
+            .map(|&(_, critical_multiplier)| critical_multiplier)
+    }
+
+    pub fn dagger() -> Self {
+        Weapon::new("dagger", Dice::new(1, 4), DamageType::Piercing)
+            .with_critical(19, 2)
+            .light()
+            .with_weight(1)
+    }
+
+    pub fn longsword() -> Self {
+        Weapon::new("longsword", Dice::new(1, 8), DamageType::Slashing)
+            .with_category(WeaponCategory::Martial)
+            .with_critical(19, 2)
+            .with_weight(4)
+    }
+
+    pub fn scimitar() -> Self {
+        Weapon::new("scimitar", Dice::new(1, 6), DamageType::Slashing)
+            .with_category(WeaponCategory::Martial)
+            .with_critical(18, 2)
+            .with_weight(4)
+    }
+
+    pub fn warhammer() -> Self {
+        Weapon::new("warhammer", Dice::new(1, 8), DamageType::Bludgeoning)
+            .with_category(WeaponCategory::Martial)
+            .with_critical(20, 3)
+            .with_weight(5)
+    }
+
+    pub fn waraxe() -> Self {
+        Weapon::new("waraxe", Dice::new(1, 10), DamageType::Slashing)
+            .with_category(WeaponCategory::Martial)
+            .with_critical(20, 3)
+            .with_weight(8)
+    }
+
+    pub fn nunchaku() -> Self {
+        Weapon::new("nunchaku", Dice::new(1, 6), DamageType::Bludgeoning)
+            .with_category(WeaponCategory::Exotic)
+            .light()
+            .with_weight(2)
+    }
+
+    pub fn longbow() -> Self {
+        Weapon::new("longbow", Dice::new(1, 8), DamageType::Piercing)
+            .with_category(WeaponCategory::Martial)
+            .with_critical(20, 3)
+            .with_weight(3)
+    }
+
+    pub fn greatsword() -> Self {
+        Weapon::new("greatsword", Dice::new(2, 6), DamageType::Slashing)
+            .with_category(WeaponCategory::Martial)
+            .with_critical(19, 2)
+            .with_weight(8)
+    }
+}
+
+impl Character {
+    // Taking up a weapon ends being disarmed.
+    pub fn equip(&mut self, weapon: Weapon) -> Option<Weapon> {
+        self.conditions.remove(Condition::Disarmed);
+        self.main_hand.replace(weapon)
+    }
+
+    // A disarmed character's weapon lies at their feet until recovered.
+    pub fn recover_weapon(&mut self) -> bool {
+        match self.dropped_weapon.take() {
+            Some(weapon) => {
+                if let Some(replaced) = self.equip(weapon) {
+                    self.dropped_weapon = Some(replaced);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    // A shield already fills the off hand.
+    pub fn equip_off_hand(&mut self, weapon: Weapon) -> Result<Option<Weapon>, ShieldError> {
+        if let Some(ref shield) = self.shield {
+            return Err(ShieldError::OffHandOccupied {
+                shield: shield.name.clone(),
+                off_hand: weapon.name,
+            });
+        }
+
+        Ok(self.off_hand.replace(weapon))
+    }
+
+    pub fn unequip(&mut self) -> Option<Weapon> {
+        self.main_hand.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use character::Race;
+    use combat::DamageRollError;
+
+    fn plus_two_waraxe() -> Weapon {
+        Weapon::waraxe()
+            .with_magic_bonus(2)
+            .with_class_critical_multiplier(Class::Rogue, 4)
+    }
+
+    #[test]
+    fn equipping_a_weapon_returns_the_previous_one() {
+        let mut character = Character::new(Class::Fighter);
+
+        assert_eq!(None, character.equip(Weapon::dagger()));
+        assert_eq!(Some(Weapon::dagger()), character.equip(Weapon::longsword()));
+        assert_eq!(Some(Weapon::longsword()), character.unequip());
+    }
+
+    #[test]
+    fn a_weapon_rolls_its_damage_dice() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.equip(Weapon::longsword());
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = fighter.attack(&attackee, 12);
+        assert_eq!(Some(Dice::new(1, 8)), attack_command.damage_dice);
+        assert_eq!(DamageType::Slashing, attack_command.damage_type);
+        assert_eq!(Some(4), attack_command.damage());
+        assert_eq!(Some(7), attack_command.with_damage_roll(7).unwrap().damage());
+    }
+
+    #[test]
+    fn a_damage_roll_must_fit_the_dice() {
+        let mut fighter = Character::new(Class::Fighter);
+        let attackee = Character::new(Class::Commoner);
+
+        assert_eq!(
+            Err(DamageRollError {
+                damage_roll: 3,
+                damage_dice: None,
+            }),
+            fighter.attack(&attackee, 12).with_damage_roll(3)
+        );
+
+        fighter.equip(Weapon::longsword());
+        assert_eq!(
+            Err(DamageRollError {
+                damage_roll: 40,
+                damage_dice: Some(Dice::new(1, 8)),
+            }),
+            fighter.attack(&attackee, 12).with_damage_roll(40)
+        );
+        assert!(fighter.attack(&attackee, 12).with_damage_roll(0).is_err());
+    }
+
+    #[test]
+    fn magic_weapons_add_to_attack_and_damage() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.equip(plus_two_waraxe());
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = fighter.attack(&attackee, 7).with_damage_roll(3).unwrap();
+        assert_eq!(2, attack_command.weapon_bonus);
+        assert!(attack_command.succeeds());
+        assert_eq!(Some(5), attack_command.damage());
+    }
+
+    #[test]
+    fn a_weapon_sets_the_critical_profile() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.equip(plus_two_waraxe());
+        let mut rogue = Character::new(Class::Rogue);
+        rogue.equip(plus_two_waraxe());
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = fighter.attack(&attackee, 20).with_damage_roll(3).unwrap();
+        assert_eq!(3, attack_command.critical_hit_multiplier);
+        assert_eq!(Some(15), attack_command.damage());
+
+        let attack_command = rogue.attack(&attackee, 20).with_damage_roll(3).unwrap();
+        assert_eq!(4, attack_command.critical_hit_multiplier);
+        assert_eq!(Some(20), attack_command.damage());
+    }
+
+    #[test]
+    fn elves_widen_a_weapons_threat_range() {
+        let mut elf = Character::new(Class::Fighter);
+        elf.race = Race::Elf;
+        elf.equip(Weapon::scimitar());
+        let attackee = Character::new(Class::Commoner);
+
+        assert_eq!(17, elf.attack(&attackee, 17).critical_threat_range);
+    }
+
+    #[test]
+    fn the_off_hand_attacks_with_the_off_hand_weapon() {
+        let mut fighter = Character::new(Class::Fighter);
+        fighter.equip(Weapon::longsword());
+        fighter.equip_off_hand(Weapon::dagger()).unwrap();
+        fighter.two_weapon_fighting = Some(Default::default());
+        let attackee = Character::new(Class::Commoner);
+
+        let attack_command = fighter.off_hand_attack(&attackee, 20).unwrap();
+        assert_eq!(Some(Dice::new(1, 4)), attack_command.damage_dice);
+        assert_eq!(-8, attack_command.two_weapon_penalty);
+    }
+}